@@ -0,0 +1,246 @@
+use crate::{answer, input, Error, Parsed, Part, Solution};
+use std::collections::BTreeMap;
+use std::env;
+use std::process;
+
+pub const USAGE: &str = "[--part 1|2] [--strict | --lenient] [--input PATH | PATH | -]";
+
+/// What to do with records that cannot be parsed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Mode {
+    /// Fail on the first record that cannot be parsed.
+    Strict,
+    /// Skip records that cannot be parsed, listing them and summarising why.
+    Lenient,
+}
+
+/// The options shared by every day's binary and the `aoc` runner.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub parts: Vec<Part>,
+    pub mode: Mode,
+    pub input: Option<String>,
+}
+
+fn parse_part(str: &str) -> Result<Part, String> {
+    match str {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("part must be 1 or 2, got {}", str)),
+    }
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Args, String> {
+        let mut parts = vec![Part::One, Part::Two];
+        let mut mode = Mode::Lenient;
+        let mut input = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => parts = vec![parse_part(args.next().ok_or("--part needs a value")?)?],
+                "--strict" => mode = Mode::Strict,
+                "--lenient" => mode = Mode::Lenient,
+                "--input" => input = Some(args.next().ok_or("--input needs a path")?.to_string()),
+                path if path == input::STDIN || !path.starts_with("--") => {
+                    input = Some(path.to_string())
+                }
+                _ => return Err(format!("unknown argument {}", arg)),
+            }
+        }
+        Ok(Args { parts, mode, input })
+    }
+}
+
+/// Applies `mode` to the rejected records, reporting them on stderr when lenient.
+pub fn accept<S: Solution>(parsed: Parsed<S::Input>, mode: Mode) -> Result<S::Input, Error> {
+    match mode {
+        Mode::Strict => parsed.strict(),
+        Mode::Lenient => {
+            for error in &parsed.rejected {
+                eprintln!("skipped {}", error);
+            }
+            if let Some(summary) = summary(&parsed.rejected, S::RECORDS) {
+                eprintln!("{}", summary);
+            }
+            Ok(parsed.value)
+        }
+    }
+}
+
+/// Counts the rejected `records` by the reason they were rejected.
+pub fn summary(rejected: &[Error], records: &str) -> Option<String> {
+    if rejected.is_empty() {
+        return None;
+    }
+    let mut reasons: BTreeMap<&str, usize> = BTreeMap::new();
+    for error in rejected {
+        *reasons.entry(error.reason()).or_insert(0) += 1;
+    }
+    summary_of_counts(&reasons, records)
+}
+
+/// Like [`summary`], for when only the count of each reason was kept.
+pub fn summary_of_counts<S: AsRef<str>>(
+    reasons: &BTreeMap<S, usize>,
+    records: &str,
+) -> Option<String> {
+    if reasons.is_empty() {
+        return None;
+    }
+    let lines = reasons
+        .iter()
+        .map(|(reason, count)| format!("  {} x {}", count, reason.as_ref()))
+        .collect::<Vec<String>>();
+    Some(format!(
+        "rejected {} {}:\n{}",
+        reasons.values().sum::<usize>(),
+        records,
+        lines.join("\n")
+    ))
+}
+
+/// Removes an option only one day understands, `name` followed by its value,
+/// from `args` before the shared options are parsed.
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) if index + 1 < args.len() => {
+            let value = args.remove(index + 1);
+            args.remove(index);
+            Ok(Some(value))
+        }
+        Some(_) => Err(format!("{} needs a value", name)),
+        None => Ok(None),
+    }
+}
+
+/// Removes a flag only one day understands from `args`, returning whether it was there.
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != len
+}
+
+/// Reads the input chosen by `args` and parses it according to its mode.
+pub fn load<S: Solution>(args: &Args, default_input: &str) -> Result<S::Input, String> {
+    let input = input::read(args.input.as_deref(), default_input).map_err(|err| err.to_string())?;
+    accept::<S>(S::parse(&input), args.mode).map_err(|err| err.to_string())
+}
+
+/// Reads the input chosen by `args`, parses it once and answers each requested part.
+pub fn answers<S: Solution>(
+    args: &Args,
+    default_input: &str,
+) -> Result<Vec<(Part, Option<String>)>, String> {
+    let input = load::<S>(args, default_input)?;
+    Ok(args
+        .parts
+        .iter()
+        .map(|&part| (part, answer::<S>(&input, part)))
+        .collect())
+}
+
+/// Entry point for a day's binary, reading `default_input` unless told otherwise.
+pub fn main<S: Solution>(default_input: &str) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    run::<S>(&args, default_input)
+}
+
+/// Answers the parts chosen by `args`, exiting with a message on failure.
+pub fn run<S: Solution>(args: &[String], default_input: &str) {
+    let args = Args::parse(args).unwrap_or_else(|err| {
+        eprintln!("{}\nusage: {}", err, USAGE);
+        process::exit(1);
+    });
+    match answers::<S>(&args, default_input) {
+        Ok(answers) => {
+            for (part, answer) in answers {
+                match answer {
+                    Some(answer) => println!("part {}: {}", part.number(), answer),
+                    None => println!("part {}: no answer", part.number()),
+                }
+            }
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(str: &str) -> Vec<String> {
+        str.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn can_parse_defaults() {
+        let expected = Args {
+            parts: vec![Part::One, Part::Two],
+            mode: Mode::Lenient,
+            input: None,
+        };
+        assert_eq!(Ok(expected), Args::parse(&[]));
+    }
+
+    #[test]
+    fn can_parse_part_and_input() {
+        let expected = Args {
+            parts: vec![Part::Two],
+            mode: Mode::Strict,
+            input: Some("input.txt".to_string()),
+        };
+        assert_eq!(
+            Ok(expected),
+            Args::parse(&args("--part 2 --strict --input input.txt"))
+        );
+    }
+
+    #[test]
+    fn can_parse_positional_stdin() {
+        assert_eq!(
+            Some("-".to_string()),
+            Args::parse(&args("-")).unwrap().input
+        );
+    }
+
+    #[test]
+    fn fails_on_unknown_part() {
+        assert!(Args::parse(&args("--part 3")).is_err());
+    }
+
+    #[test]
+    fn summary_counts_rejections_by_reason() {
+        let rejected = vec![
+            Error::parse("a", 1, "no height"),
+            Error::parse("b", 1, "bad year"),
+            Error::parse("c", 1, "no height"),
+        ];
+        assert_eq!(
+            Some("rejected 3 passports:\n  1 x bad year\n  2 x no height".to_string()),
+            summary(&rejected, "passports")
+        );
+        assert_eq!(None, summary(&[], "passports"));
+    }
+
+    #[test]
+    fn can_take_day_specific_options() {
+        let mut args = args("--target 100 --part 1 --explain input.txt");
+        assert_eq!(
+            Ok(Some("100".to_string())),
+            take_option(&mut args, "--target")
+        );
+        assert_eq!(Ok(None), take_option(&mut args, "--count"));
+        assert!(take_flag(&mut args, "--explain"));
+        assert_eq!(vec!["--part", "1", "input.txt"], args);
+    }
+
+    #[test]
+    fn option_without_value_fails() {
+        let mut args = args("--part 1 --target");
+        assert!(take_option(&mut args, "--target").is_err());
+    }
+}
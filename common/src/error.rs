@@ -0,0 +1,172 @@
+use std::fmt;
+
+/// Everything that can go wrong between reading a puzzle input and solving it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Error {
+    /// The input could not be read at all.
+    Input(String),
+    /// A record could not be parsed. `line` and `column` are 1-based, with the
+    /// column counted in characters, and `text` is the offending line.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+}
+
+impl Error {
+    /// A parse error at `column` of a single line of `text`.
+    pub fn parse<S: Into<String>>(text: &str, column: usize, reason: S) -> Error {
+        Error::Parse {
+            line: 1,
+            column,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// A parse error at byte `offset` of a record that may span several lines.
+    pub fn parse_at<S: Into<String>>(record: &str, offset: usize, reason: S) -> Error {
+        let before = &record[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let line_end = record[offset..]
+            .find('\n')
+            .map_or(record.len(), |index| offset + index);
+        Error::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: record[line_start..line_end].trim_end().to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn reason(&self) -> &str {
+        match self {
+            Error::Input(reason) => reason,
+            Error::Parse { reason, .. } => reason,
+        }
+    }
+
+    /// Moves an error found in a record to where that record starts in the input.
+    pub fn starting_at(self, first_line: usize) -> Error {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                text,
+                reason,
+            } => Error::Parse {
+                line: line + first_line - 1,
+                column,
+                text,
+                reason,
+            },
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Input(reason) => write!(f, "{}", reason),
+            Error::Parse {
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "line {}, column {}: {} in {:?}",
+                line, column, reason, text
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A parsed input along with every record that had to be skipped.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Parsed<T> {
+    pub value: T,
+    pub rejected: Vec<Error>,
+}
+
+impl<T> Parsed<T> {
+    /// An input where nothing was skipped.
+    pub fn new(value: T) -> Parsed<T> {
+        Parsed {
+            value,
+            rejected: Vec::new(),
+        }
+    }
+
+    /// The parsed value, or the first rejected record if there were any.
+    pub fn strict(self) -> Result<T, Error> {
+        match self.rejected.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(self.value),
+        }
+    }
+}
+
+impl<T> Parsed<Vec<T>> {
+    /// Keeps the records that parsed and sets aside the ones that did not.
+    pub fn records<I>(results: I) -> Parsed<Vec<T>>
+    where
+        I: IntoIterator<Item = Result<T, Error>>,
+    {
+        let mut parsed = Parsed::new(Vec::new());
+        for result in results {
+            match result {
+                Ok(record) => parsed.value.push(record),
+                Err(error) => parsed.rejected.push(error),
+            }
+        }
+        parsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_locate_error_in_multi_line_record() {
+        let expected = Error::Parse {
+            line: 2,
+            column: 4,
+            text: "cd ef".to_string(),
+            reason: "bad".to_string(),
+        };
+        assert_eq!(expected, Error::parse_at("ab\ncd ef\ngh", 6, "bad"));
+    }
+
+    #[test]
+    fn can_move_error_to_record_start() {
+        let error = Error::parse("abc", 2, "bad").starting_at(10);
+        assert_eq!("line 10, column 2: bad in \"abc\"", error.to_string());
+    }
+
+    #[test]
+    fn records_are_split_into_parsed_and_rejected() {
+        let results = vec![Ok(1), Err(Error::parse("x", 1, "bad")), Ok(3)];
+        let parsed = Parsed::records(results);
+        assert_eq!(vec![1, 3], parsed.value);
+        assert_eq!(1, parsed.rejected.len());
+    }
+
+    #[test]
+    fn strict_fails_on_first_rejected_record() {
+        let results = vec![
+            Ok(1),
+            Err(Error::parse("x", 1, "first")),
+            Err(Error::parse("y", 1, "second")),
+        ];
+        let result = Parsed::records(results).strict();
+        assert_eq!(Err(Error::parse("x", 1, "first")), result);
+        assert_eq!(Ok(vec![1]), Parsed::records(vec![Ok(1)]).strict());
+    }
+}
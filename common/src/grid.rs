@@ -0,0 +1,347 @@
+use crate::{input, Error};
+use std::fmt;
+use std::str::FromStr;
+
+/// What a [`Grid`] does with coordinates that fall off its edges.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Edges {
+    /// Columns repeat forever to the left and right, rows stop at the top and bottom.
+    WrapHorizontally,
+    /// Both columns and rows repeat forever.
+    Wrap,
+    /// Coordinates past an edge are pulled back onto it.
+    Clamp,
+    /// Anything outside the grid is out of bounds and has no cell.
+    Bounded,
+}
+
+/// A cell that can be read from and written as a single character of a map.
+pub trait Cell: Sized {
+    fn from_char(character: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+/// `#` is `true` and `.` is `false`, as in most of the puzzle maps.
+impl Cell for bool {
+    fn from_char(character: char) -> Option<bool> {
+        match character {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+/// Any character, for maps whose meaning is decided elsewhere.
+impl Cell for char {
+    fn from_char(character: char) -> Option<char> {
+        Some(character)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// A rectangular grid of cells indexed by `(x, y)`, with `x` counting columns
+/// from the left and `y` counting rows from the top.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    edges: Edges,
+}
+
+const ADJACENT: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl<T> Grid<T> {
+    /// A grid from its rows, which all have to be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "row {} has {} cells but the first row has {}",
+                index + 1,
+                rows[index].len(),
+                width
+            ));
+        }
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            edges: Edges::Bounded,
+        })
+    }
+
+    /// The same grid, treating its edges as `edges`.
+    pub fn with_edges(mut self, edges: Edges) -> Grid<T> {
+        self.edges = edges;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Where `(x, y)` lands on the grid once its edges are taken into account.
+    pub fn locate(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        if self.is_empty() {
+            return None;
+        }
+        let (width, height) = (self.width as isize, self.height as isize);
+        let inside = |value: isize, size: isize| (0..size).contains(&value);
+        let (x, y) = match self.edges {
+            Edges::WrapHorizontally if inside(y, height) => (x.rem_euclid(width), y),
+            Edges::WrapHorizontally => return None,
+            Edges::Wrap => (x.rem_euclid(width), y.rem_euclid(height)),
+            Edges::Clamp => (x.clamp(0, width - 1), y.clamp(0, height - 1)),
+            Edges::Bounded if inside(x, width) && inside(y, height) => (x, y),
+            Edges::Bounded => return None,
+        };
+        Some((x as usize, y as usize))
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        let (x, y) = self.locate(x, y)?;
+        self.cells.get(y * self.width + x)
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        let (x, y) = self.locate(x, y)?;
+        self.cells.get_mut(y * self.width + x)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every cell along with its coordinates, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    fn around<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        let mut seen = vec![(x, y)];
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let position = self.locate(x as isize + dx, y as isize + dy)?;
+            // a clamped or tiny wrapped grid can fold a neighbour back onto the
+            // cell itself or onto a neighbour on the other side
+            if seen.contains(&position) {
+                return None;
+            }
+            seen.push(position);
+            Some((position, &self.cells[position.1 * self.width + position.0]))
+        })
+    }
+
+    /// The up to four cells above, left, right and below `(x, y)`.
+    pub fn adjacent(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.around(x, y, &ADJACENT)
+    }
+
+    /// The up to eight cells around `(x, y)`, diagonals included.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.around(x, y, &SURROUNDING)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            edges: self.edges,
+        }
+    }
+}
+
+/// An empty grid, which has no cells whatever its edges.
+impl<T> Default for Grid<T> {
+    fn default() -> Grid<T> {
+        Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+            edges: Edges::Bounded,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Reads a map with one row per line, skipping blank lines, turning each
+    /// character into a cell with `cell` and reporting the position of any
+    /// character it rejects.
+    pub fn parse_with<F>(input: &str, mut cell: F) -> Result<Grid<T>, Error>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let mut rows = Vec::new();
+        for (index, line) in input::lines(input).enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let row = line
+                .char_indices()
+                .map(|(offset, character)| {
+                    cell(character).map_err(|reason| {
+                        Error::parse_at(line, offset, reason).starting_at(index + 1)
+                    })
+                })
+                .collect::<Result<Vec<T>, Error>>()?;
+            if let Some(first) = rows
+                .first()
+                .map(Vec::len)
+                .filter(|&width| width != row.len())
+            {
+                return Err(Error::parse_at(
+                    line,
+                    0,
+                    format!(
+                        "expected {} columns like the first row, got {}",
+                        first,
+                        row.len()
+                    ),
+                )
+                .starting_at(index + 1));
+            }
+            rows.push(row);
+        }
+        Ok(Grid::from_rows(rows).expect("rows were checked to be the same length"))
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    /// Reads a map of [`Cell`]s, as [`Grid::parse_with`] does.
+    pub fn parse(input: &str) -> Result<Grid<T>, Error> {
+        Grid::parse_with(input, |character| {
+            T::from_char(character).ok_or_else(|| format!("unknown map character {:?}", character))
+        })
+    }
+}
+
+impl<T: Cell> FromStr for Grid<T> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Grid<T>, Error> {
+        Grid::parse(input)
+    }
+}
+
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell.to_char())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "..#\n#..\n.#.";
+
+    fn map(edges: Edges) -> Grid<bool> {
+        MAP.parse::<Grid<bool>>().unwrap().with_edges(edges)
+    }
+
+    #[test]
+    fn display_round_trips_the_map() {
+        let grid = map(Edges::Bounded);
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert_eq!(MAP, grid.to_string());
+        assert_eq!(Ok(grid.clone()), grid.to_string().parse());
+    }
+
+    #[test]
+    fn edges_decide_what_is_off_the_map() {
+        assert_eq!(Some(&true), map(Edges::WrapHorizontally).get(5, 0));
+        assert_eq!(Some(&true), map(Edges::WrapHorizontally).get(-3, 1));
+        assert_eq!(None, map(Edges::WrapHorizontally).get(0, 3));
+        assert_eq!(Some(&true), map(Edges::Wrap).get(2, -3));
+        assert_eq!(Some((2, 0)), map(Edges::Clamp).locate(7, -2));
+        assert_eq!(None, map(Edges::Bounded).get(3, 0));
+        assert_eq!(
+            None,
+            Grid::<bool>::default().with_edges(Edges::Wrap).get(0, 0)
+        );
+    }
+
+    #[test]
+    fn can_iterate_neighbours() {
+        let bounded = map(Edges::Bounded);
+        let corner = bounded.neighbours(0, 0).map(|(position, _)| position);
+        assert_eq!(vec![(1, 0), (0, 1), (1, 1)], corner.collect::<Vec<_>>());
+        assert_eq!(4, bounded.adjacent(1, 1).count());
+        let wrapped = map(Edges::Wrap);
+        let trees = wrapped.neighbours(0, 0).filter(|(_, &tree)| tree);
+        assert_eq!(3, trees.count());
+    }
+
+    #[test]
+    fn neighbours_on_a_tiny_wrapped_grid_are_found_once() {
+        let grid = ".#\n#.".parse::<Grid<bool>>().unwrap();
+        let grid = grid.with_edges(Edges::Wrap);
+        let positions = grid.neighbours(0, 0).map(|(position, _)| position);
+        assert_eq!(vec![(1, 1), (0, 1), (1, 0)], positions.collect::<Vec<_>>());
+        assert_eq!(2, grid.adjacent(0, 0).count());
+    }
+
+    #[test]
+    fn reports_unknown_characters_and_ragged_rows() {
+        assert_eq!(
+            "line 2, column 3: unknown map character 'x' in \"#.x\"",
+            "..#\n#.x".parse::<Grid<bool>>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 3, column 1: expected 3 columns like the first row, got 2 in \"#.\"",
+            "..#\n\n#.".parse::<Grid<bool>>().unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{Error, Parsed};
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+
+/// The path that reads puzzle input from stdin instead of a file.
+pub const STDIN: &str = "-";
+
+/// Reads puzzle input from `path`, from stdin when `path` is `-`, or from
+/// `default` when no path is given.
+pub fn read(path: Option<&str>, default: &str) -> Result<String, Error> {
+    match path.unwrap_or(default) {
+        STDIN => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| Error::Input(format!("could not read input from stdin: {}", err)))?;
+            Ok(input)
+        }
+        path => fs::read_to_string(path)
+            .map_err(|err| Error::Input(format!("could not read input from {}: {}", path, err))),
+    }
+}
+
+/// Opens the same input as [`read`] for reading a line at a time, so inputs
+/// too large to hold in memory can still be worked through.
+pub fn open(path: Option<&str>, default: &str) -> Result<Box<dyn BufRead>, Error> {
+    match path.unwrap_or(default) {
+        STDIN => Ok(Box::new(BufReader::new(io::stdin()))),
+        path => File::open(path)
+            .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+            .map_err(|err| Error::Input(format!("could not read input from {}: {}", path, err))),
+    }
+}
+
+/// Splits `input` into lines, accepting both `\n` and `\r\n` endings whatever
+/// the platform, and ignoring any blank lines at the end of the input.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    input.trim_end_matches(['\r', '\n']).lines()
+}
+
+/// Splits `input` into groups of lines separated by one or more blank lines.
+pub fn paragraphs(input: &str) -> impl Iterator<Item = Vec<&str>> {
+    numbered_paragraphs(input).map(|(_, paragraph)| paragraph)
+}
+
+/// Like [`paragraphs`], along with the line number each paragraph starts on.
+pub fn numbered_paragraphs(input: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    let mut lines = lines(input)
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+        let (first_line, _) = *lines.peek()?;
+        let paragraph: Vec<&str> = lines
+            .by_ref()
+            .map(|(_, line)| line)
+            .take_while(|line| !line.trim().is_empty())
+            .collect();
+        Some((first_line, paragraph))
+    })
+}
+
+/// Splits `input` into rows of characters, skipping blank lines.
+pub fn grid(input: &str) -> Vec<Vec<char>> {
+    lines(input)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+/// Parses every non-blank line with `parse`, recording the real line number of
+/// any line that fails.
+pub fn parse_lines<T, F>(input: &str, mut parse: F) -> Parsed<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T, Error>,
+{
+    parse_numbered_lines(input, |_, line| parse(line))
+}
+
+/// Like [`parse_lines`], also handing `parse` the real line number of each
+/// line for records that need to remember where they came from.
+pub fn parse_numbered_lines<T, F>(input: &str, mut parse: F) -> Parsed<Vec<T>>
+where
+    F: FnMut(usize, &str) -> Result<T, Error>,
+{
+    Parsed::records(
+        lines(input)
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                parse(index + 1, line).map_err(|error| error.starting_at(index + 1))
+            }),
+    )
+}
+
+/// Parses every paragraph with `parse`, handing it the paragraph's lines joined
+/// by `\n` and recording the real line number of any paragraph that fails.
+pub fn parse_paragraphs<T, F>(input: &str, mut parse: F) -> Parsed<Vec<T>>
+where
+    F: FnMut(&str) -> Result<T, Error>,
+{
+    Parsed::records(numbered_paragraphs(input).map(|(first_line, paragraph)| {
+        parse(&paragraph.join("\n")).map_err(|error| error.starting_at(first_line))
+    }))
+}
+
+/// Parses each line of `input` as a number.
+pub fn numbers<T>(input: &str) -> Parsed<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_lines(input, |line| {
+        line.trim()
+            .parse::<T>()
+            .map_err(|err| Error::parse(line, 1, err.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_missing_input_file() {
+        let result = read(None, "does/not/exist.txt");
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .starts_with("could not read input from does/not/exist.txt"));
+    }
+
+    #[test]
+    fn lines_handle_crlf_and_trailing_blank_lines() {
+        let input = "abc\r\ndef\r\n\r\n\r\n";
+        assert_eq!(vec!["abc", "def"], lines(input).collect::<Vec<&str>>());
+    }
+
+    #[test]
+    fn paragraphs_split_on_blank_lines_with_any_ending() {
+        let input = "a\r\nb\r\n\r\nc\n\n\nd\ne\n";
+        let expected = vec![vec!["a", "b"], vec!["c"], vec!["d", "e"]];
+        assert_eq!(expected, paragraphs(input).collect::<Vec<Vec<&str>>>());
+    }
+
+    #[test]
+    fn grid_skips_blank_lines() {
+        let input = "#.\r\n.#\r\n\r\n";
+        assert_eq!(vec![vec!['#', '.'], vec!['.', '#']], grid(input));
+    }
+
+    #[test]
+    fn can_parse_numbers() {
+        let input = "1\r\n20\r\nthree\r\n";
+        let result = numbers::<u32>(input);
+        assert_eq!(vec![1, 20], result.value);
+        assert_eq!(
+            vec![Error::Parse {
+                line: 3,
+                column: 1,
+                text: "three".to_string(),
+                reason: "invalid digit found in string".to_string(),
+            }],
+            result.rejected
+        );
+    }
+
+    #[test]
+    fn parse_paragraphs_reports_real_line_numbers() {
+        let input = "a\n\nb\nc x\n";
+        let result = parse_paragraphs(input, |paragraph| match paragraph.find('x') {
+            Some(offset) => Err(Error::parse_at(paragraph, offset, "found x")),
+            None => Ok(paragraph.len()),
+        });
+        assert_eq!(vec![1], result.value);
+        assert_eq!(
+            "line 4, column 3: found x in \"c x\"",
+            result.rejected[0].to_string()
+        );
+    }
+}
@@ -0,0 +1,87 @@
+pub mod cli;
+mod error;
+pub mod grid;
+pub mod input;
+
+pub use error::{Error, Parsed};
+
+use std::fmt::Display;
+
+/// Which half of a day's puzzle to answer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A day's puzzle: parse the raw input once into a typed model, then answer
+/// each part from that model.
+///
+/// Records that cannot be parsed are set aside in [`Parsed::rejected`] rather
+/// than failing the whole input. A part returns `None` when the input has no
+/// answer (or the part is not solved yet) rather than panicking.
+pub trait Solution {
+    /// What the input's records are called when reporting rejected ones.
+    const RECORDS: &'static str = "records";
+
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Parsed<Self::Input>;
+    fn part_one(input: &Self::Input) -> Option<Self::PartOne>;
+    fn part_two(input: &Self::Input) -> Option<Self::PartTwo>;
+}
+
+/// Answers `part` from already parsed input, rendering the answer for display.
+pub fn answer<S: Solution>(input: &S::Input, part: Part) -> Option<String> {
+    match part {
+        Part::One => S::part_one(input).map(|answer| answer.to_string()),
+        Part::Two => S::part_two(input).map(|answer| answer.to_string()),
+    }
+}
+
+/// Parses `input` and answers `part`, rendering the answer for display.
+pub fn solve<S: Solution>(input: &str, part: Part) -> Option<String> {
+    answer::<S>(&S::parse(input).value, part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        fn parse(input: &str) -> Parsed<Vec<u32>> {
+            Parsed::new(input.split(',').filter_map(|n| n.parse().ok()).collect())
+        }
+
+        fn part_one(numbers: &Vec<u32>) -> Option<u32> {
+            Some(numbers.iter().sum())
+        }
+
+        fn part_two(_: &Vec<u32>) -> Option<u32> {
+            None
+        }
+    }
+
+    #[test]
+    fn can_solve_each_part() {
+        assert_eq!(Some("6".to_string()), solve::<Sum>("1,2,3", Part::One));
+        assert_eq!(None, solve::<Sum>("1,2,3", Part::Two));
+    }
+}
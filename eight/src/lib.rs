@@ -0,0 +1,180 @@
+use common::{input, Error, Parsed, Solution};
+use itertools::*;
+use regex::Regex;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Operation {
+    Acc(isize),
+    Jmp(isize),
+    Nop,
+}
+
+impl Operation {
+    pub fn parse_line(str: &str) -> Result<Operation, Error> {
+        let re = Regex::new(r"(?P<operation>\w+) (?P<argument>[-+]\d+)").unwrap();
+        let captures = re
+            .captures(str)
+            .ok_or_else(|| Error::parse(str, 1, "expected `<operation> <+/-argument>`"))?;
+        let operation = captures.name("operation").unwrap();
+        let argument = captures.name("argument").unwrap();
+        let argument_value = argument
+            .as_str()
+            .parse::<isize>()
+            .map_err(|_| Error::parse_at(str, argument.start(), "couldn't parse argument"))?;
+
+        match operation.as_str() {
+            "acc" => Ok(Operation::Acc(argument_value)),
+            "jmp" => Ok(Operation::Jmp(argument_value)),
+            "nop" => Ok(Operation::Nop),
+            unrecognized => Err(Error::parse_at(
+                str,
+                operation.start(),
+                format!("unrecognized operation {}", unrecognized),
+            )),
+        }
+    }
+
+    /// Repairs the single corrupted `jmp`/`nop` so the program terminates and
+    /// returns the accumulator of the repaired run, or `None` when the program
+    /// leaves itself before looping or has nothing to repair.
+    pub fn run_fix(program: &[Operation]) -> Option<isize> {
+        let mut position: isize = 0;
+        let mut path: Vec<isize> = Vec::new();
+        while !path.contains(&(position + 1)) {
+            let operation = program.get(position as usize);
+            match operation {
+                Some(Operation::Acc(_)) => {
+                    position += 1;
+                }
+                Some(Operation::Jmp(argument)) => {
+                    path.push(position);
+                    position += argument;
+                }
+                Some(Operation::Nop) => {
+                    path.push(position);
+                    position += 1;
+                }
+                None => return None,
+            }
+        }
+        // if final position is a Nop change to Jmp and jump the amount of Jmp's ahead
+        let (change_position, change) = path.iter().sorted().rev().find_map(|pos| {
+            let length = program.len();
+            let position = *pos as usize;
+            let operation = program.get(*pos as usize);
+            match operation {
+                Some(Operation::Jmp(_)) => {
+                    let remaining_program = program[(position + 1)..length]
+                        .iter()
+                        .filter(|op| matches!(op, Operation::Jmp(_)))
+                        .count();
+                    if remaining_program == 0 {
+                        Some((position as isize, Operation::Nop))
+                    } else {
+                        None
+                    }
+                }
+                Some(Operation::Nop) => program[(position + 1)..length]
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .find_map(|(idx, op)| match op {
+                        Operation::Jmp(_) => {
+                            Some((position as isize, Operation::Jmp(idx as isize)))
+                        }
+                        _ => None,
+                    }),
+                _ => None,
+            }
+        })?;
+        let mut fixed_program = program.to_vec();
+        fixed_program[change_position as usize] = change;
+        Some(Operation::run(&fixed_program))
+    }
+
+    /// Runs until an instruction is about to repeat and returns the accumulator.
+    pub fn run(program: &[Operation]) -> isize {
+        let mut acc: isize = 0;
+        let mut position: isize = 0;
+        let mut path: Vec<isize> = Vec::new();
+        while !path.contains(&(position + 1)) {
+            let operation = program.get(position as usize);
+            match operation {
+                Some(Operation::Acc(argument)) => {
+                    position += 1;
+                    acc += argument;
+                    path.push(position);
+                }
+                Some(Operation::Jmp(argument)) => {
+                    position += argument;
+                    path.push(position);
+                }
+                Some(Operation::Nop) => {
+                    position += 1;
+                    path.push(position);
+                }
+                None => {
+                    path.push(position + 1);
+                }
+            }
+        }
+        acc
+    }
+}
+
+pub fn parse_program(input: &str) -> Parsed<Vec<Operation>> {
+    input::parse_lines(input, Operation::parse_line)
+}
+
+pub struct HandheldHalting;
+
+impl Solution for HandheldHalting {
+    const RECORDS: &'static str = "instructions";
+
+    type Input = Vec<Operation>;
+    type PartOne = isize;
+    type PartTwo = isize;
+
+    fn parse(input: &str) -> Parsed<Vec<Operation>> {
+        parse_program(input)
+    }
+
+    fn part_one(program: &Vec<Operation>) -> Option<isize> {
+        Some(Operation::run(program))
+    }
+
+    fn part_two(program: &Vec<Operation>) -> Option<isize> {
+        Operation::run_fix(program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_operations() {
+        assert_eq!(Ok(Operation::Acc(-99)), Operation::parse_line("acc -99"));
+        assert_eq!(Ok(Operation::Jmp(4)), Operation::parse_line("jmp +4"));
+        assert_eq!(Ok(Operation::Nop), Operation::parse_line("nop +0"));
+    }
+
+    #[test]
+    fn reports_unrecognized_operation() {
+        let expected = Error::Parse {
+            line: 1,
+            column: 1,
+            text: "mul +2".to_string(),
+            reason: "unrecognized operation mul".to_string(),
+        };
+        assert_eq!(Err(expected), Operation::parse_line("mul +2"));
+    }
+
+    #[test]
+    fn cannot_fix_a_program_that_never_loops() {
+        let program = vec![Operation::Nop, Operation::Acc(1)];
+        assert_eq!(None, Operation::run_fix(&program));
+    }
+}
@@ -0,0 +1,217 @@
+use common::{input, Error, Parsed, Solution};
+use itertools::Itertools;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BoardingPass {
+    pub column: usize,
+    pub row: usize,
+}
+
+impl BoardingPass {
+    pub fn seat_id(&self) -> usize {
+        (self.row * 8) + self.column
+    }
+}
+
+/// Narrows down a seat index from upper (`true`) and lower (`false`) halves.
+pub fn binary_search_index(bits: Vec<bool>) -> usize {
+    let max_num: usize = 2_usize.pow(bits.len() as u32);
+    let (min, _) = bits.into_iter().fold((0, max_num), |(min, max), bit| {
+        let range = max - min;
+        if bit {
+            (min + (range / 2), max) // true (upper)
+        } else {
+            (min, max - (range / 2)) // false (lower)
+        }
+    });
+    min
+}
+
+const ROW_CHARACTERS: usize = 7;
+const SEAT_CHARACTERS: usize = 10;
+
+/// Parses a boarding pass such as `FBFBBFFRLR`, where seven `F`/`B` pick the
+/// row and the three `L`/`R` after them pick the column.
+pub fn parse_boarding_pass(str: &str) -> Result<BoardingPass, Error> {
+    let mut row_binary: Vec<bool> = Vec::new();
+    let mut column_binary: Vec<bool> = Vec::new();
+    for (index, (offset, character)) in str.char_indices().enumerate() {
+        let in_row = index < ROW_CHARACTERS;
+        match character {
+            _ if index >= SEAT_CHARACTERS => {
+                return Err(Error::parse_at(
+                    str,
+                    offset,
+                    format!("expected {} seat characters", SEAT_CHARACTERS),
+                ))
+            }
+            'B' | 'F' if in_row => row_binary.push(character == 'B'),
+            'R' | 'L' if !in_row => column_binary.push(character == 'R'),
+            'B' | 'F' | 'R' | 'L' => {
+                let expected = if in_row { "F or B" } else { "L or R" };
+                return Err(Error::parse_at(
+                    str,
+                    offset,
+                    format!("expected {}, got {:?}", expected, character),
+                ));
+            }
+            _ => {
+                return Err(Error::parse_at(
+                    str,
+                    offset,
+                    format!("unknown seat character {:?}", character),
+                ))
+            }
+        }
+    }
+    if column_binary.len() + row_binary.len() < SEAT_CHARACTERS {
+        return Err(Error::parse_at(
+            str,
+            str.len(),
+            format!("expected {} seat characters", SEAT_CHARACTERS),
+        ));
+    }
+    Ok(BoardingPass {
+        row: binary_search_index(row_binary),
+        column: binary_search_index(column_binary),
+    })
+}
+
+pub fn parse_boarding_passes(input: &str) -> Parsed<Vec<BoardingPass>> {
+    input::parse_lines(input, parse_boarding_pass)
+}
+
+pub fn max_seat_id(boarding_passes: &[BoardingPass]) -> Option<usize> {
+    boarding_passes
+        .iter()
+        .map(|boarding_pass| boarding_pass.seat_id())
+        .max()
+}
+
+/// Finds the one empty seat whose neighbouring seat ids are both taken, or
+/// `None` when no seat has a gap like that.
+pub fn missing_seat_id(boarding_passes: &[BoardingPass]) -> Option<usize> {
+    let max_seat_id = max_seat_id(boarding_passes)?;
+    let (_, missing_id) = boarding_passes
+        .iter()
+        .sorted_by(|a, b| Ord::cmp(&b.seat_id(), &a.seat_id()))
+        .fold((max_seat_id + 1, None), |(acc, cur), boarding_pass| {
+            let delta = acc - boarding_pass.seat_id();
+            if delta == 2 {
+                (boarding_pass.seat_id(), Some(boarding_pass.seat_id() + 1))
+            } else {
+                (boarding_pass.seat_id(), cur)
+            }
+        });
+    missing_id
+}
+
+pub struct BinaryBoarding;
+
+impl Solution for BinaryBoarding {
+    const RECORDS: &'static str = "boarding passes";
+
+    type Input = Vec<BoardingPass>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Parsed<Vec<BoardingPass>> {
+        parse_boarding_passes(input)
+    }
+
+    fn part_one(boarding_passes: &Vec<BoardingPass>) -> Option<usize> {
+        max_seat_id(boarding_passes)
+    }
+
+    fn part_two(boarding_passes: &Vec<BoardingPass>) -> Option<usize> {
+        missing_seat_id(boarding_passes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_binary_search_index_min() {
+        let input = vec![false, false, false, false, false, false, false];
+        let output = binary_search_index(input);
+        assert_eq!(0, output);
+    }
+
+    #[test]
+    fn can_find_binary_search_index_max() {
+        let input = vec![true, true, true, true, true, true, true];
+        let output = binary_search_index(input);
+        assert_eq!(127, output);
+    }
+
+    #[test]
+    fn can_find_binary_search_index_other() {
+        let input = vec![true, false, true];
+        let output = binary_search_index(input);
+        assert_eq!(5, output);
+    }
+
+    #[test]
+    fn can_find_binary_search_index() {
+        let input = vec![false, true, false, true, true, false, false];
+        let output = binary_search_index(input);
+        assert_eq!(44, output);
+    }
+
+    #[test]
+    fn can_parse_boarding_pass() {
+        let expected = BoardingPass { row: 44, column: 5 };
+        assert_eq!(Ok(expected), parse_boarding_pass("FBFBBFFRLR"));
+    }
+
+    #[test]
+    fn reports_unknown_seat_character() {
+        let expected = Error::Parse {
+            line: 1,
+            column: 4,
+            text: "FBFXBFFRLR".to_string(),
+            reason: "unknown seat character 'X'".to_string(),
+        };
+        assert_eq!(Err(expected), parse_boarding_pass("FBFXBFFRLR"));
+    }
+
+    #[test]
+    fn has_no_missing_seat_without_a_gap() {
+        let boarding_passes = vec![
+            BoardingPass { row: 44, column: 5 },
+            BoardingPass { row: 44, column: 6 },
+        ];
+        assert_eq!(None, missing_seat_id(&boarding_passes));
+        let gap = vec![
+            BoardingPass { row: 44, column: 5 },
+            BoardingPass { row: 44, column: 7 },
+        ];
+        assert_eq!(Some(358), missing_seat_id(&gap));
+    }
+
+    #[test]
+    fn reports_seats_of_the_wrong_shape() {
+        let reason = |str: &str| parse_boarding_pass(str).unwrap_err().to_string();
+        assert_eq!(
+            "line 1, column 4: expected F or B, got 'R' in \"FBFRBFFRLR\"",
+            reason("FBFRBFFRLR")
+        );
+        assert_eq!(
+            "line 1, column 9: expected L or R, got 'F' in \"FBFBBFFRFR\"",
+            reason("FBFBBFFRFR")
+        );
+        assert_eq!(
+            "line 1, column 11: expected 10 seat characters in \"FBFBBFFRLRR\"",
+            reason("FBFBBFFRLRR")
+        );
+        assert_eq!(
+            "line 1, column 4: expected 10 seat characters in \"FBF\"",
+            reason("FBF")
+        );
+        assert!(parse_boarding_pass(&"F".repeat(64)).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use common::{input, Error, Parsed, Solution};
+use schema::{check_syntax, Document, Level, Schema, Value};
+use std::sync::LazyLock;
+
+pub mod report;
+pub mod schema;
+
+const BIRTH_YEAR: &str = "byr";
+const ISSUED_YEAR: &str = "iyr";
+const EXPIRATION_YEAR: &str = "eyr";
+const HEIGHT: &str = "hgt";
+const HAIR_COLOUR: &str = "hcl";
+const EYE_COLOUR: &str = "ecl";
+const PASSPORT_ID: &str = "pid";
+const COUNTRY_ID: &str = "cid";
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Passport {
+    pub birth_year: u16,
+    pub issued_year: u16,
+    pub expiration_year: u16,
+    pub height: u16,
+    pub hair_colour: String,
+    pub eye_colour: String,
+    pub passport_id: String,
+    pub country_id: Option<String>,
+}
+
+fn number(document: &Document, key: &str) -> u16 {
+    match document.get(key) {
+        Some(Value::Year(number)) | Some(Value::Measurement(number, _)) => *number,
+        _ => 0,
+    }
+}
+
+fn text(document: &Document, key: &str) -> Option<String> {
+    match document.get(key) {
+        Some(Value::Text(text)) => Some(text.clone()),
+        _ => None,
+    }
+}
+
+impl From<&Document> for Passport {
+    /// Takes the fields of a document checked against [`Schema::passport`].
+    fn from(document: &Document) -> Passport {
+        Passport {
+            birth_year: number(document, BIRTH_YEAR),
+            issued_year: number(document, ISSUED_YEAR),
+            expiration_year: number(document, EXPIRATION_YEAR),
+            height: number(document, HEIGHT),
+            hair_colour: text(document, HAIR_COLOUR).unwrap_or_default(),
+            eye_colour: text(document, EYE_COLOUR).unwrap_or_default(),
+            passport_id: text(document, PASSPORT_ID).unwrap_or_default(),
+            country_id: text(document, COUNTRY_ID),
+        }
+    }
+}
+
+static PASSPORT: LazyLock<Schema> = LazyLock::new(Schema::passport);
+
+/// Parses and validates a single passport entry, failing on the first invalid field.
+pub fn parse_entry(entry: &str) -> Result<Passport, Error> {
+    PASSPORT
+        .validate(entry, Level::Valid)
+        .map(|document| Passport::from(&document))
+}
+
+/// Parses every blank line separated passport, setting aside the invalid ones.
+pub fn parse_input(input: &str) -> Parsed<Vec<Passport>> {
+    input::parse_paragraphs(input, |entry| {
+        PASSPORT
+            .validate(entry, Level::Valid)
+            .map(|document| Passport::from(&document))
+    })
+}
+
+/// How far a passport got through validation. Problems are given by their
+/// line in the whole input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Checked {
+    /// Every field is there and valid.
+    Valid(Passport),
+    /// Every required field is there, but not every value is valid.
+    Present(Vec<Error>),
+    /// Some required field is missing, along with any other problems.
+    Missing(Vec<Error>),
+}
+
+impl Checked {
+    /// The highest level the passport passed, if any.
+    pub fn level(&self) -> Option<Level> {
+        match self {
+            Checked::Valid(_) => Some(Level::Valid),
+            Checked::Present(_) => Some(Level::Present),
+            Checked::Missing(_) => None,
+        }
+    }
+}
+
+/// Checks every passport at both levels, only setting aside those that are
+/// not written as `key:value` tokens at all.
+pub fn check_input(input: &str) -> Parsed<Vec<Checked>> {
+    Parsed::records(
+        input::numbered_paragraphs(input).map(|(first_line, paragraph)| {
+            let entry = paragraph.join("\n");
+            check_syntax(&entry).map_err(|error| error.starting_at(first_line))?;
+            Ok(match PASSPORT.check(&entry, Level::Valid) {
+                Ok(document) => Checked::Valid(Passport::from(&document)),
+                Err(problems) => {
+                    let problems = problems
+                        .into_iter()
+                        .map(|problem| problem.starting_at(first_line))
+                        .collect();
+                    match PASSPORT.check(&entry, Level::Present) {
+                        Ok(_) => Checked::Present(problems),
+                        Err(_) => Checked::Missing(problems),
+                    }
+                }
+            })
+        }),
+    )
+}
+
+pub struct PassportProcessing;
+
+impl Solution for PassportProcessing {
+    const RECORDS: &'static str = "passports";
+
+    type Input = Vec<Checked>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Parsed<Vec<Checked>> {
+        check_input(input)
+    }
+
+    /// Passports with every required field, whatever the fields hold.
+    fn part_one(passports: &Vec<Checked>) -> Option<usize> {
+        Some(
+            passports
+                .iter()
+                .filter(|passport| passport.level().is_some())
+                .count(),
+        )
+    }
+
+    /// Passports whose fields are all valid too.
+    fn part_two(passports: &Vec<Checked>) -> Option<usize> {
+        Some(
+            passports
+                .iter()
+                .filter(|passport| passport.level() == Some(Level::Valid))
+                .count(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_multiple_entries() {
+        let input = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+        let result = parse_input(input);
+        assert_eq!(4, result.value.len());
+    }
+
+    #[test]
+    fn can_parse_passport_entry() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+    byr:1937 iyr:2017 cid:147 hgt:183cm";
+        let expected = Passport {
+            expiration_year: 2020,
+            issued_year: 2017,
+            passport_id: 860033327.to_string(),
+            birth_year: 1937,
+            hair_colour: "#fffffd".to_string(),
+            country_id: Some(147.to_string()),
+            eye_colour: "gry".to_string(),
+            height: 183,
+        };
+        let result = parse_entry(input);
+        assert_eq!(Ok(expected), result);
+    }
+
+    #[test]
+    fn fails_to_parse() {
+        let input = "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929";
+        let result = parse_entry(input);
+        let expected = Error::Parse {
+            line: 1,
+            column: 1,
+            text: "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884".to_string(),
+            reason: "could not find height".to_string(),
+        };
+        assert_eq!(Err(expected), result);
+    }
+
+    #[test]
+    fn reports_where_an_invalid_field_is() {
+        let input = "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929 hgt:12ft";
+        let result = parse_entry(input);
+        let expected = Error::Parse {
+            line: 2,
+            column: 26,
+            text: "hcl:#cfa07d byr:1929 hgt:12ft".to_string(),
+            reason: "Invalid measurement ft".to_string(),
+        };
+        assert_eq!(Err(expected), result);
+    }
+
+    #[test]
+    fn keeps_passports_missing_fields_and_places_their_problems() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013 eyr:2024 ecl:wat
+pid:760753108 byr:1931 hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid 166559648";
+        let parsed = check_input(input);
+        let levels = parsed.value.iter().map(Checked::level).collect::<Vec<_>>();
+        assert_eq!(vec![Some(Level::Valid), None, Some(Level::Present)], levels);
+        match &parsed.value[2] {
+            Checked::Present(problems) => assert_eq!(
+                "line 7, column 35: wat is not a recognised eye colour \
+                 in \"hcl:#ae17e1 iyr:2013 eyr:2024 ecl:wat\"",
+                problems[0].to_string()
+            ),
+            checked => panic!("expected a present passport, got {:?}", checked),
+        }
+        assert_eq!(
+            "line 10, column 22: expected `key:value`, got pid \
+             in \"hcl:#cfa07d eyr:2025 pid 166559648\"",
+            parsed.rejected[0].to_string()
+        );
+        assert_eq!(Some(2), PassportProcessing::part_one(&parsed.value));
+        assert_eq!(Some(1), PassportProcessing::part_two(&parsed.value));
+    }
+}
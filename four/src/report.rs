@@ -0,0 +1,82 @@
+use crate::schema::{Level, Schema};
+use common::{input, Error};
+
+/// Everything wrong with one passport, so it can be fixed in one go.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Report {
+    /// The line the passport starts on.
+    pub line: usize,
+    pub problems: Vec<Error>,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Checks every passport in `input` against `schema` to `level`, valid ones included.
+pub fn report(schema: &Schema, input: &str, level: Level) -> Vec<Report> {
+    input::numbered_paragraphs(input)
+        .map(|(line, paragraph)| Report {
+            line,
+            problems: match schema.check(&paragraph.join("\n"), level) {
+                Ok(_) => Vec::new(),
+                Err(problems) => problems
+                    .into_iter()
+                    .map(|problem| problem.starting_at(line))
+                    .collect(),
+            },
+        })
+        .collect()
+}
+
+/// Lists the problems with each invalid passport, then how many were valid.
+pub fn to_text(reports: &[Report]) -> String {
+    let mut text = String::new();
+    for report in reports.iter().filter(|report| !report.is_valid()) {
+        let plural = if report.problems.len() == 1 { "" } else { "s" };
+        text.push_str(&format!(
+            "passport at line {} has {} problem{}:\n",
+            report.line,
+            report.problems.len(),
+            plural
+        ));
+        for problem in &report.problems {
+            text.push_str(&format!("  {}\n", problem));
+        }
+    }
+    let valid = reports.iter().filter(|report| report.is_valid()).count();
+    text.push_str(&format!(
+        "{} of {} passports are valid",
+        valid,
+        reports.len()
+    ));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:wat cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929";
+
+    #[test]
+    fn reports_every_problem_with_each_passport() {
+        let reports = report(&Schema::passport(), INPUT, Level::Valid);
+        assert_eq!(2, reports.len());
+        assert!(reports[0].is_valid());
+        assert_eq!(4, reports[1].line);
+        let expected = "passport at line 4 has 2 problems:
+  line 4, column 1: could not find height in \"iyr:2013 ecl:wat cid:350 eyr:2023 pid:028048884\"
+  line 4, column 14: wat is not a recognised eye colour in \"iyr:2013 ecl:wat cid:350 eyr:2023 pid:028048884\"
+1 of 2 passports are valid";
+        assert_eq!(expected, to_text(&reports));
+        let present = report(&Schema::passport(), INPUT, Level::Present);
+        assert_eq!(1, present[1].problems.len());
+    }
+}
@@ -0,0 +1,349 @@
+use common::Error;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// A unit a measurement can be given in and the range it has to fall in.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Unit {
+    pub unit: String,
+    pub min: u16,
+    pub max: u16,
+}
+
+/// What a field's value has to look like.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Kind {
+    /// A year from `min` to `max`, both included.
+    Year { min: u16, max: u16 },
+    /// A number directly followed by one of the units, such as `183cm`.
+    Measurement { units: Vec<Unit> },
+    /// `#` followed by six lowercase hex digits.
+    HexColour,
+    /// One of a fixed list of values.
+    Enum { values: Vec<String> },
+    /// Exactly `length` digits, leading zeros and all.
+    Digits { length: usize },
+    /// Anything at all.
+    Text,
+}
+
+/// A field's value once it has been checked against its [`Kind`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Value {
+    Year(u16),
+    Measurement(u16, String),
+    Text(String),
+}
+
+fn is_between(num: u16, min: u16, max: u16) -> Result<u16, String> {
+    if num >= min && num <= max {
+        Ok(num)
+    } else {
+        Err(format!("{} was out of bounds ({},{})", num, min, max))
+    }
+}
+
+impl Kind {
+    /// Checks `value` of the field called `name`, explaining what is wrong with it.
+    pub fn check(&self, name: &str, value: &str) -> Result<Value, String> {
+        match self {
+            Kind::Year { min, max } => value
+                .parse::<u16>()
+                .map_err(|_| format!("could not parse {} into a year", value))
+                .and_then(|year| is_between(year, *min, *max))
+                .map(Value::Year),
+            Kind::Measurement { units } => {
+                let unit = value.trim_start_matches(|c: char| c.is_ascii_digit());
+                let magnitude = &value[..value.len() - unit.len()];
+                if unit.is_empty() {
+                    return Err(format!("{} has no unit", value));
+                }
+                let range = units
+                    .iter()
+                    .find(|range| range.unit == unit)
+                    .ok_or_else(|| format!("Invalid measurement {}", unit))?;
+                magnitude
+                    .parse::<u16>()
+                    .map_err(|_| format!("could not parse {} to u16", magnitude))
+                    .and_then(|num| is_between(num, range.min, range.max))
+                    .map(|num| Value::Measurement(num, unit.to_string()))
+            }
+            Kind::HexColour => match value.strip_prefix('#') {
+                Some(hex) if hex.chars().count() != 6 => {
+                    Err("hex string is not 7 characters".to_string())
+                }
+                Some(hex) if hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) => {
+                    Ok(Value::Text(value.to_string()))
+                }
+                _ => Err(format!("{} is not a valid hex string", value)),
+            },
+            Kind::Enum { values } if values.iter().any(|allowed| allowed == value) => {
+                Ok(Value::Text(value.to_string()))
+            }
+            Kind::Enum { .. } => Err(format!("{} is not a recognised {}", value, name)),
+            Kind::Digits { length }
+                if value.len() == *length && value.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                Ok(Value::Text(value.to_string()))
+            }
+            Kind::Digits { .. } => Err(format!("{} is not a valid {}", value, name)),
+            Kind::Text => Ok(Value::Text(value.to_string())),
+        }
+    }
+}
+
+fn required() -> bool {
+    true
+}
+
+/// A field a document can have, under `key`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Field {
+    pub key: String,
+    /// What to call the field when reporting problems, the key if not given.
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "required")]
+    pub required: bool,
+    #[serde(flatten)]
+    pub kind: Kind,
+}
+
+impl Field {
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.key)
+    }
+}
+
+/// How closely a document is checked.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Level {
+    /// Every required field is there, whatever it holds.
+    Present,
+    /// Every field that is there holds a value of its kind.
+    Valid,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Level, String> {
+        match str {
+            "present" => Ok(Level::Present),
+            "valid" => Ok(Level::Valid),
+            _ => Err(format!("expected a level of present or valid, got {}", str)),
+        }
+    }
+}
+
+/// The checked values of a document, by key. Values only checked for being
+/// present are kept as [`Value::Text`].
+pub type Document = BTreeMap<String, Value>;
+
+type Details = HashMap<String, (usize, String)>;
+
+static KEY_VALUE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?P<key>\S*):(?P<value>\S*)\b").unwrap());
+
+/// Checks that every whitespace separated token of `entry` is written as
+/// `key:value`, whatever the key and value are.
+pub fn check_syntax(entry: &str) -> Result<(), Error> {
+    for token in entry.split_whitespace() {
+        if let None | Some(0) = token.find(':') {
+            let offset = token.as_ptr() as usize - entry.as_ptr() as usize;
+            return Err(Error::parse_at(
+                entry,
+                offset,
+                format!("expected `key:value`, got {}", token),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The `key:value` pairs of an entry, with the byte offset of each value.
+fn details(entry: &str) -> Details {
+    KEY_VALUE
+        .captures_iter(entry)
+        .map(|key_value| {
+            let value = &key_value["value"];
+            let offset = key_value.name("value").map_or(0, |value| value.start());
+            (key_value["key"].to_string(), (offset, value.to_string()))
+        })
+        .collect()
+}
+
+/// The fields a kind of document has, read from TOML or JSON so new kinds of
+/// document can be checked without a new build.
+///
+/// In TOML each field is a `[[fields]]` table with `key`, an optional `name`,
+/// `required` (true unless set) and a `type` of `year` (with `min` and `max`),
+/// `measurement` (with `units`, each a `unit`, `min` and `max`), `hex_colour`,
+/// `enum` (with `values`), `digits` (with `length`) or `text`. JSON takes the
+/// same shape as an object with a `fields` array.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Schema {
+    pub fields: Vec<Field>,
+}
+
+impl Schema {
+    /// The rules for passports given in the puzzle.
+    pub fn passport() -> Schema {
+        Schema::from_toml(include_str!("passport.toml")).expect("the passport schema is valid")
+    }
+
+    pub fn from_toml(str: &str) -> Result<Schema, String> {
+        toml::from_str(str).map_err(|err| format!("invalid schema: {}", err))
+    }
+
+    pub fn from_json(str: &str) -> Result<Schema, String> {
+        serde_json::from_str(str).map_err(|err| format!("invalid schema: {}", err))
+    }
+
+    /// Reads a schema from `path`, as JSON if it ends in `.json` and as TOML otherwise.
+    pub fn load(path: &str) -> Result<Schema, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("could not read schema from {}: {}", path, err))?;
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("json") => Schema::from_json(&contents),
+            _ => Schema::from_toml(&contents),
+        }
+    }
+
+    /// Checks every field of `entry` to `level` in the order the schema lists
+    /// them, collecting a problem for each one that is missing or invalid.
+    pub fn check(&self, entry: &str, level: Level) -> Result<Document, Vec<Error>> {
+        let details = details(entry);
+        let mut document = Document::new();
+        let mut problems = Vec::new();
+        for field in &self.fields {
+            let (offset, value) = match details.get(&field.key) {
+                Some(found) => found,
+                None if field.required => {
+                    problems.push(Error::parse_at(
+                        entry,
+                        0,
+                        format!("could not find {}", field.name()),
+                    ));
+                    continue;
+                }
+                None => continue,
+            };
+            let checked = match level {
+                Level::Present => Ok(Value::Text(value.to_string())),
+                Level::Valid => field.kind.check(field.name(), value),
+            };
+            match checked {
+                Ok(value) => {
+                    document.insert(field.key.clone(), value);
+                }
+                Err(reason) => problems.push(Error::parse_at(entry, *offset, reason)),
+            }
+        }
+        if problems.is_empty() {
+            Ok(document)
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Like [`Schema::check`], failing with only the first problem.
+    pub fn validate(&self, entry: &str, level: Level) -> Result<Document, Error> {
+        self.check(entry, level)
+            .map_err(|mut problems| problems.remove(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_each_kind_of_field() {
+        let schema = Schema::passport();
+        let kind = |key: &str| {
+            let field = schema.fields.iter().find(|field| field.key == key).unwrap();
+            move |value: &str| field.kind.check(field.name(), value)
+        };
+        assert_eq!(Ok(Value::Year(2002)), kind("byr")("2002"));
+        assert!(kind("byr")("2003").is_err());
+        assert_eq!(
+            Ok(Value::Measurement(60, "in".to_string())),
+            kind("hgt")("60in")
+        );
+        assert!(kind("hgt")("190in").is_err());
+        assert_eq!(Err("190 has no unit".to_string()), kind("hgt")("190"));
+        assert!(kind("hcl")("#123abc").is_ok());
+        assert!(kind("hcl")("#123abz").is_err());
+        assert!(kind("hcl")("123abc").is_err());
+        assert_eq!(
+            Err("wat is not a recognised eye colour".to_string()),
+            kind("ecl")("wat")
+        );
+        assert!(kind("pid")("000000001").is_ok());
+        assert!(kind("pid")("0123456789").is_err());
+    }
+
+    #[test]
+    fn can_load_a_schema_from_json() {
+        let schema = Schema::from_json(
+            r#"{"fields": [
+                {"key": "exp", "name": "expiry", "type": "year", "min": 2020, "max": 2030},
+                {"key": "cls", "type": "enum", "values": ["A", "B"], "required": false}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!("expiry", schema.fields[0].name());
+        assert_eq!("cls", schema.fields[1].name());
+        let document = schema.validate("exp:2025 cls:B", Level::Valid).unwrap();
+        assert_eq!(Some(&Value::Text("B".to_string())), document.get("cls"));
+        assert!(schema.validate("exp:2025", Level::Valid).is_ok());
+        assert_eq!(
+            "could not find expiry",
+            schema.validate("cls:A", Level::Valid).unwrap_err().reason()
+        );
+        assert!(Schema::from_json(r#"{"fields": [{"key": "x", "type": "colour"}]}"#).is_err());
+    }
+
+    #[test]
+    fn syntax_needs_a_key_and_colon_in_every_token() {
+        assert_eq!(Ok(()), check_syntax("byr:1937 hgt:\ncid:147"));
+        assert_eq!(
+            "line 2, column 11: expected `key:value`, got 1937 in \"hgt:183cm 1937\"",
+            check_syntax("ecl:gry\nhgt:183cm 1937")
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(check_syntax("ecl:gry :2017").is_err());
+    }
+
+    #[test]
+    fn check_reports_every_problem() {
+        let entry = "ecl:wat pid:000000001 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017";
+        let schema = Schema::passport();
+        let problems = schema.check(entry, Level::Valid).unwrap_err();
+        let reasons = problems.iter().map(Error::reason).collect::<Vec<&str>>();
+        assert_eq!(
+            vec![
+                "could not find height",
+                "wat is not a recognised eye colour"
+            ],
+            reasons
+        );
+        assert_eq!(
+            Err(problems[0].clone()),
+            schema.validate(entry, Level::Valid)
+        );
+        let present = schema.check(entry, Level::Present);
+        assert_eq!(1, present.unwrap_err().len());
+    }
+}
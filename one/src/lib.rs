@@ -0,0 +1,53 @@
+use common::{input, Parsed, Solution};
+use std::marker::PhantomData;
+
+pub mod number;
+pub mod sum;
+
+pub use number::{Entry, Product};
+pub use sum::{count_sums, find_all_sums, find_sum, Combination};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+/// The sum the expense report entries have to add up to.
+pub const TARGET: u16 = 2020;
+
+/// Parses one expense entry per line, setting aside lines that are not numbers
+/// or do not fit in `T`.
+pub fn parse_numbers<T: Entry>(input: &str) -> Parsed<Vec<T>> {
+    input::numbers::<T>(input)
+}
+
+/// Finds `count` entries that sum to 2020 and returns their product.
+pub fn product_of_combination<T: Entry + From<u16>>(
+    numbers: &[T],
+    count: usize,
+) -> Option<Product<T>> {
+    find_sum(numbers, T::from(TARGET), count).map(|combination| combination.product())
+}
+
+/// The expense report puzzle over entries of type `T`.
+pub struct Expenses<T>(PhantomData<T>);
+
+/// The expense report puzzle as it is usually run, with 64-bit entries.
+pub type ReportRepair = Expenses<i64>;
+
+impl<T: Entry + From<u16>> Solution for Expenses<T> {
+    const RECORDS: &'static str = "expense entries";
+
+    type Input = Vec<T>;
+    type PartOne = Product<T>;
+    type PartTwo = Product<T>;
+
+    fn parse(input: &str) -> Parsed<Vec<T>> {
+        parse_numbers(input)
+    }
+
+    fn part_one(numbers: &Vec<T>) -> Option<Product<T>> {
+        product_of_combination(numbers, 2)
+    }
+
+    fn part_two(numbers: &Vec<T>) -> Option<Product<T>> {
+        product_of_combination(numbers, 3)
+    }
+}
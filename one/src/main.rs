@@ -1,34 +1,94 @@
-use itertools::Itertools;
+use common::cli::{self, Args};
+use num_bigint::BigInt;
+use one::{
+    count_sums, find_all_sums, find_sum, Combination, Entry, Expenses, DEFAULT_INPUT, TARGET,
+};
+use std::env;
+use std::process;
 
-#[cfg(windows)]
-const LINE_ENDING: &'static str = "\r\n";
-#[cfg(not(windows))]
-const LINE_ENDING: &'static str = "\n";
+const USAGE: &str =
+    "usage: one [--target N] [--count K] [--all | --count-only] [--type i64|u64|big]";
 
-fn main() {
-    let input: &str = include_str!("input.txt");
-    let number_list = input
-        .split(LINE_ENDING)
-        .map(|number_str| number_str.parse::<i32>())
-        .filter_map(Result::ok);
+fn parse_number<T: std::str::FromStr>(
+    name: &str,
+    value: Option<String>,
+) -> Result<Option<T>, String> {
+    value
+        .map(|value| {
+            value
+                .parse::<T>()
+                .map_err(|_| format!("{} must be a number, got {}", name, value))
+        })
+        .transpose()
+}
 
-    let two_combinations = number_list
-        .clone()
-        .combinations(2)
-        .find(|combinations| combinations.iter().sum::<i32>() == 2020)
-        .map(|combinations| combinations.iter().product::<i32>());
+fn describe<T: Entry>(combination: &Combination<T>, target: &T) -> String {
+    let values = combination
+        .values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>();
+    let lines = combination
+        .indices
+        .iter()
+        .map(|index| (index + 1).to_string())
+        .collect::<Vec<String>>();
+    format!(
+        "{} = {} (entries {}), product {}",
+        values.join(" + "),
+        target,
+        lines.join(", "),
+        combination.product()
+    )
+}
 
-    let three_combinations = number_list
-        .clone()
-        .combinations(3)
-        .find(|combinations| combinations.iter().sum::<i32>() == 2020)
-        .map(|combinations| combinations.iter().product::<i32>());
+fn run(mut args: Vec<String>) -> Result<(), String> {
+    match cli::take_option(&mut args, "--type")?.as_deref() {
+        None | Some("i64") => search::<i64>(args),
+        Some("u64") => search::<u64>(args),
+        Some("big") => search::<BigInt>(args),
+        Some(other) => Err(format!("--type must be i64, u64 or big, got {}", other)),
+    }
+}
 
-    // let three_combinations: Vec<Vec<i32>> = number_list.combinations(3).collect();
+fn search<T: Entry + From<u16>>(mut args: Vec<String>) -> Result<(), String> {
+    let target = parse_number::<T>("--target", cli::take_option(&mut args, "--target")?)?;
+    let count = parse_number::<usize>("--count", cli::take_option(&mut args, "--count")?)?;
+    let all = cli::take_flag(&mut args, "--all");
+    let count_only = cli::take_flag(&mut args, "--count-only");
+    if target.is_none() && count.is_none() && !all && !count_only {
+        cli::run::<Expenses<T>>(&args, DEFAULT_INPUT);
+        return Ok(());
+    }
 
-    println!(
-        "two numbers that sum to 2020 then multiplied are := {}, three numbers that sum to 2020 then multiplied are := {}",
-        two_combinations.unwrap(),
-        three_combinations.unwrap()
-    );
+    let args = Args::parse(&args)?;
+    let numbers = cli::load::<Expenses<T>>(&args, DEFAULT_INPUT)?;
+    let target = target.unwrap_or_else(|| T::from(TARGET));
+    let count = count.unwrap_or(2);
+    if count_only {
+        println!("{}", count_sums(&numbers, target.clone(), count));
+        return Ok(());
+    }
+    let combinations = if all {
+        find_all_sums(&numbers, target.clone(), count)
+    } else {
+        find_sum(&numbers, target.clone(), count)
+            .into_iter()
+            .collect()
+    };
+    if combinations.is_empty() {
+        println!("no {} entries sum to {}", count, target);
+    }
+    for combination in &combinations {
+        println!("{}", describe(combination, &target));
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(args) {
+        eprintln!("{}\n{} {}", err, USAGE, cli::USAGE);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,101 @@
+use num_bigint::BigInt;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, One, Zero};
+use std::fmt;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// A number an expense report can hold: any integer that can check its own
+/// arithmetic for overflow and widen into a [`BigInt`] when it cannot keep up.
+pub trait Entry:
+    Clone
+    + Ord
+    + Hash
+    + Display
+    + Zero
+    + One
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + Into<BigInt>
+    + FromStr<Err = <Self as Entry>::ParseError>
+{
+    type ParseError: Display;
+}
+
+impl<T> Entry for T
+where
+    T: Clone
+        + Ord
+        + Hash
+        + Display
+        + Zero
+        + One
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + Into<BigInt>
+        + FromStr,
+    T::Err: Display,
+{
+    type ParseError = T::Err;
+}
+
+/// The product of some entries, kept in the entries' own type while it fits
+/// and carried on as a [`BigInt`] once it would overflow.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Product<T> {
+    Exact(T),
+    Big(BigInt),
+}
+
+impl<T: Entry> Product<T> {
+    pub fn of(values: &[T]) -> Product<T> {
+        let exact = values
+            .iter()
+            .try_fold(T::one(), |product, value| product.checked_mul(value));
+        match exact {
+            Some(product) => Product::Exact(product),
+            None => Product::Big(values.iter().cloned().map(Into::into).product()),
+        }
+    }
+
+    pub fn to_big(&self) -> BigInt {
+        match self {
+            Product::Exact(product) => product.clone().into(),
+            Product::Big(product) => product.clone(),
+        }
+    }
+}
+
+impl<T: Display> Display for Product<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Product::Exact(product) => write!(f, "{}", product),
+            Product::Big(product) => write!(f, "{}", product),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_products_that_fit_exact() {
+        assert_eq!(Product::Exact(514579), Product::of(&[1721, 299]));
+        assert_eq!(Product::Exact(1u64), Product::of(&[]));
+    }
+
+    #[test]
+    fn widens_products_that_overflow() {
+        let product = Product::of(&[i64::MAX, 4, -3]);
+        let expected = BigInt::from(i64::MAX) * -12i64;
+        assert_eq!(Product::Big(expected.clone()), product);
+        assert_eq!(expected.to_string(), product.to_string());
+        assert_eq!(
+            Product::Big(BigInt::from(u64::MAX) * 2u8),
+            Product::of(&[u64::MAX, 2])
+        );
+    }
+}
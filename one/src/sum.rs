@@ -0,0 +1,424 @@
+use crate::number::{Entry, Product};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Entries from the expense report that add up to a target, along with where
+/// they are in the report.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Combination<T> {
+    pub indices: Vec<usize>,
+    pub values: Vec<T>,
+}
+
+impl<T: Entry> Combination<T> {
+    fn from_indices(numbers: &[T], mut indices: Vec<usize>) -> Combination<T> {
+        indices.sort_unstable();
+        let values = indices
+            .iter()
+            .map(|&index| numbers[index].clone())
+            .collect();
+        Combination { indices, values }
+    }
+
+    pub fn product(&self) -> Product<T> {
+        Product::of(&self.values)
+    }
+}
+
+/// Finds `count` distinct entries of `numbers` that sum to `target`.
+///
+/// Pairs use a hash lookup, triples sort and close in with two pointers, and
+/// anything larger meets in the middle, looking at about n^⌈count / 2⌉
+/// halves. Sums that would overflow `T` never match.
+pub fn find_sum<T: Entry>(numbers: &[T], target: T, count: usize) -> Option<Combination<T>> {
+    let indices = match count {
+        0 if target.is_zero() => Some(Vec::new()),
+        0 => None,
+        1 => numbers
+            .iter()
+            .position(|number| *number == target)
+            .map(|index| vec![index]),
+        2 => two_sum(numbers, &target),
+        3 => three_sum(numbers, &target),
+        _ => meet_in_the_middle(numbers, &target, count),
+    }?;
+    Some(Combination::from_indices(numbers, indices))
+}
+
+/// How `a + b` compares to `target`, even when the sum overflows.
+fn compare_sum<T: Entry>(a: &T, b: &T, target: &T) -> Ordering {
+    match a.checked_add(b) {
+        Some(sum) => sum.cmp(target),
+        None if *a > T::zero() => Ordering::Greater,
+        None => Ordering::Less,
+    }
+}
+
+/// How `count` copies of `value` added together compare to `target`, even
+/// when the total overflows.
+fn compare_multiple<T: Entry>(value: &T, count: usize, target: &T) -> Ordering {
+    let mut total = T::zero();
+    for _ in 0..count {
+        match total.checked_add(value) {
+            Some(sum) => total = sum,
+            None if *value > T::zero() => return Ordering::Greater,
+            None => return Ordering::Less,
+        }
+    }
+    total.cmp(target)
+}
+
+fn checked_sum<'a, T: Entry + 'a, I: IntoIterator<Item = &'a T>>(values: I) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::zero(), |sum, value| sum.checked_add(value))
+}
+
+fn two_sum<T: Entry>(numbers: &[T], target: &T) -> Option<Vec<usize>> {
+    let mut seen: HashMap<&T, usize> = HashMap::new();
+    for (index, number) in numbers.iter().enumerate() {
+        let other = target
+            .checked_sub(number)
+            .and_then(|rest| seen.get(&rest).copied());
+        if let Some(other) = other {
+            return Some(vec![other, index]);
+        }
+        seen.entry(number).or_insert(index);
+    }
+    None
+}
+
+/// The entries in ascending order, each paired with its index in the report.
+fn sorted_entries<T: Entry>(numbers: &[T]) -> Vec<(T, usize)> {
+    let mut sorted: Vec<(T, usize)> = numbers.iter().cloned().zip(0..).collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+fn three_sum<T: Entry>(numbers: &[T], target: &T) -> Option<Vec<usize>> {
+    let sorted = sorted_entries(numbers);
+    for first in 0..sorted.len() {
+        let remaining = match target.checked_sub(&sorted[first].0) {
+            Some(remaining) => remaining,
+            None => continue,
+        };
+        let (mut low, mut high) = (first + 1, sorted.len().saturating_sub(1));
+        while low < high {
+            match compare_sum(&sorted[low].0, &sorted[high].0, &remaining) {
+                Ordering::Less => low += 1,
+                Ordering::Greater => high -= 1,
+                Ordering::Equal => {
+                    return Some(vec![sorted[first].1, sorted[low].1, sorted[high].1])
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Every way of picking `count` increasing indices below `len`, in
+/// lexicographic order and built one at a time.
+struct IndexCombinations {
+    len: usize,
+    next: Option<Vec<usize>>,
+}
+
+impl IndexCombinations {
+    fn new(len: usize, count: usize) -> IndexCombinations {
+        let next = if count <= len {
+            Some((0..count).collect())
+        } else {
+            None
+        };
+        IndexCombinations { len, next }
+    }
+}
+
+impl Iterator for IndexCombinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let current = self.next.take()?;
+        let mut next = current.clone();
+        // bump the rightmost index that still has room, then pack the rest
+        // straight after it
+        let count = next.len();
+        let room = (0..count)
+            .rev()
+            .find(|&at| next[at] < self.len - count + at);
+        if let Some(at) = room {
+            next[at] += 1;
+            for after in at + 1..count {
+                next[after] = next[after - 1] + 1;
+            }
+            self.next = Some(next);
+        }
+        Some(current)
+    }
+}
+
+fn meet_in_the_middle<T: Entry>(numbers: &[T], target: &T, count: usize) -> Option<Vec<usize>> {
+    // any sorted set of indices splits into a lower half whose largest index
+    // sits below the smallest index of the upper half, so for each sum only
+    // the lower half ending earliest is worth keeping
+    let lower_count = count / 2;
+    let mut lower_halves: HashMap<T, Vec<usize>> = HashMap::new();
+    for lower in IndexCombinations::new(numbers.len(), lower_count) {
+        let sum = match checked_sum(lower.iter().map(|&index| &numbers[index])) {
+            Some(sum) => sum,
+            None => continue,
+        };
+        let kept = lower_halves.entry(sum).or_insert_with(|| lower.clone());
+        if lower.last() < kept.last() {
+            *kept = lower;
+        }
+    }
+    IndexCombinations::new(numbers.len(), count - lower_count).find_map(|upper| {
+        let sum = checked_sum(upper.iter().map(|&index| &numbers[index]))?;
+        let lower = lower_halves.get(&target.checked_sub(&sum)?)?;
+        if lower.last().is_some_and(|&last| last >= upper[0]) {
+            return None;
+        }
+        Some(lower.iter().chain(upper.iter()).copied().collect())
+    })
+}
+
+/// Every distinct combination of `count` entries that sums to `target`.
+///
+/// Combinations are distinct by their values, so a report holding the same
+/// number twice does not list the same sum twice, though both copies can be
+/// used together. They come back ordered by their values.
+pub fn find_all_sums<T: Entry>(numbers: &[T], target: T, count: usize) -> Vec<Combination<T>> {
+    let sorted = sorted_entries(numbers);
+    let mut combinations = Vec::new();
+    let mut chosen = Vec::with_capacity(count);
+    collect_sums(&sorted, 0, target, count, &mut chosen, &mut |indices| {
+        combinations.push(Combination::from_indices(numbers, indices.to_vec()))
+    });
+    combinations
+}
+
+/// How many combinations [`find_all_sums`] would return, without building them.
+pub fn count_sums<T: Entry>(numbers: &[T], target: T, count: usize) -> usize {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    count_from(&sorted, 0, &target, count)
+}
+
+fn collect_sums<T: Entry, F: FnMut(&[usize])>(
+    sorted: &[(T, usize)],
+    start: usize,
+    target: T,
+    count: usize,
+    chosen: &mut Vec<usize>,
+    found: &mut F,
+) {
+    if count == 0 {
+        if target.is_zero() {
+            found(chosen);
+        }
+        return;
+    }
+    match sorted.last() {
+        Some((largest, _)) if compare_multiple(largest, count, &target) != Ordering::Less => {}
+        _ => return,
+    }
+    for first in start..sorted.len() {
+        // a value already tried at this position would only repeat its sums
+        if first > start && sorted[first].0 == sorted[first - 1].0 {
+            continue;
+        }
+        if sorted.len() - first < count {
+            break;
+        }
+        let value = &sorted[first].0;
+        if compare_multiple(value, count, &target) == Ordering::Greater {
+            break;
+        }
+        if let Some(remaining) = target.checked_sub(value) {
+            chosen.push(sorted[first].1);
+            collect_sums(sorted, first + 1, remaining, count - 1, chosen, found);
+            chosen.pop();
+        }
+    }
+}
+
+fn count_from<T: Entry>(sorted: &[T], start: usize, target: &T, count: usize) -> usize {
+    match count {
+        0 => target.is_zero() as usize,
+        2 => count_pairs(&sorted[start..], target),
+        _ => (start..sorted.len())
+            .filter(|&first| first == start || sorted[first] != sorted[first - 1])
+            .take_while(|&first| {
+                compare_multiple(&sorted[first], count, target) != Ordering::Greater
+            })
+            .filter_map(|first| Some((first, target.checked_sub(&sorted[first])?)))
+            .map(|(first, remaining)| count_from(sorted, first + 1, &remaining, count - 1))
+            .sum(),
+    }
+}
+
+/// Distinct pairs of values in an ascending slice that sum to `target`.
+fn count_pairs<T: Entry>(sorted: &[T], target: &T) -> usize {
+    let mut pairs = 0;
+    let (mut low, mut high) = (0, sorted.len());
+    while low + 1 < high {
+        match compare_sum(&sorted[low], &sorted[high - 1], target) {
+            Ordering::Less => low += 1,
+            Ordering::Greater => high -= 1,
+            Ordering::Equal => {
+                pairs += 1;
+                let (low_value, high_value) = (&sorted[low], &sorted[high - 1]);
+                while low < high && sorted[low] == *low_value {
+                    low += 1;
+                }
+                while high > low && sorted[high - 1] == *high_value {
+                    high -= 1;
+                }
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    const EXAMPLE: [i32; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    #[test]
+    fn can_find_pair() {
+        let expected = Combination {
+            indices: vec![0, 3],
+            values: vec![1721, 299],
+        };
+        assert_eq!(Some(expected), find_sum(&EXAMPLE, 2020, 2));
+    }
+
+    #[test]
+    fn can_find_triple() {
+        let expected = Combination {
+            indices: vec![1, 2, 4],
+            values: vec![979, 366, 675],
+        };
+        assert_eq!(Some(expected), find_sum(&EXAMPLE, 2020, 3));
+    }
+
+    #[test]
+    fn can_find_larger_combinations() {
+        let numbers = [5, 9, 1, 12, 7, 3, 20];
+        for (target, count) in [(33, 4), (45, 5), (52, 6)] {
+            let result = find_sum(&numbers, target, count).unwrap();
+            assert_eq!(target, result.values.iter().sum::<i32>());
+            assert_eq!(count, result.indices.len());
+            assert!(result.indices.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn meets_in_the_middle_quickly_with_repeated_values() {
+        let started = std::time::Instant::now();
+        let ones = [1; 200];
+        let result = find_sum(&ones, 6, 6).unwrap();
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], result.indices);
+        assert_eq!(None, find_sum(&ones, 7, 6));
+        let numbers: Vec<i32> = (1..=200).collect();
+        let result = find_sum(&numbers, 600, 6).unwrap();
+        assert_eq!(600, result.values.iter().sum::<i32>());
+        assert!(started.elapsed() < std::time::Duration::from_secs(20));
+    }
+
+    #[test]
+    fn does_not_reuse_an_entry() {
+        assert_eq!(None, find_sum(&[1010, 1], 2020, 2));
+        assert_eq!(None, find_sum(&[505, 1, 2, 3], 2020, 4));
+        assert!(find_sum(&[1010, 1010], 2020, 2).is_some());
+    }
+
+    #[test]
+    fn can_find_every_combination() {
+        let values = find_all_sums(&EXAMPLE, 2020, 2)
+            .into_iter()
+            .map(|combination| combination.values)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![vec![1721, 299]], values);
+
+        let numbers = [1, 4, 2, 3, 2, 3, 0];
+        let values = find_all_sums(&numbers, 5, 2)
+            .into_iter()
+            .map(|combination| combination.values)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![vec![1, 4], vec![2, 3]], values);
+        assert_eq!(2, count_sums(&numbers, 5, 2));
+    }
+
+    #[test]
+    fn uses_repeated_values_only_as_often_as_they_appear() {
+        let numbers = [3, 1, 3, 5];
+        let combinations = find_all_sums(&numbers, 6, 2);
+        assert_eq!(2, combinations.len());
+        assert_eq!(vec![1, 5], combinations[0].values);
+        assert_eq!(vec![0, 2], combinations[1].indices);
+        assert_eq!(0, count_sums(&numbers, 6, 3));
+        assert_eq!(1, count_sums(&numbers, 7, 3));
+        assert_eq!(0, count_sums(&[3, 5], 6, 2));
+    }
+
+    #[test]
+    fn counting_agrees_with_finding() {
+        let numbers = [-4, 7, 1, 1, 0, 3, -2, 5, 3, 8, -4];
+        for count in 0..6 {
+            for target in -10..20 {
+                assert_eq!(
+                    find_all_sums(&numbers, target, count).len(),
+                    count_sums(&numbers, target, count),
+                    "{} entries summing to {}",
+                    count,
+                    target
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn works_with_wider_and_unbounded_entries() {
+        let numbers: Vec<u64> = vec![u64::MAX - 1, 3, 1, u64::MAX - 3];
+        let result = find_sum(&numbers, u64::MAX, 2).unwrap();
+        assert_eq!(vec![0, 2], result.indices);
+        assert_eq!(2, count_sums(&numbers, u64::MAX, 2));
+        assert_eq!(None, find_sum(&numbers, 2, 2));
+
+        let big: Vec<BigInt> = vec![
+            BigInt::from(u64::MAX) * 4u8,
+            BigInt::from(7),
+            BigInt::from(-2),
+        ];
+        let target = BigInt::from(u64::MAX) * 4u8 + 5u8;
+        let result = find_sum(&big, target.clone(), 3).unwrap();
+        assert_eq!(vec![0, 1, 2], result.indices);
+        assert_eq!(1, find_all_sums(&big, target, 3).len());
+    }
+
+    #[test]
+    fn overflowing_sums_never_match() {
+        let numbers = [i64::MAX, i64::MAX, 1, -1];
+        assert_eq!(None, find_sum(&numbers, -2, 2));
+        assert_eq!(0, count_sums(&numbers, -2, 2));
+        assert!(find_all_sums(&numbers, -1, 3).is_empty());
+        assert_eq!(None, find_sum(&numbers, -1, 3));
+        let product = find_sum(&[i64::MAX - 2, 2], i64::MAX, 2).unwrap().product();
+        assert_eq!(Product::Big(BigInt::from(i64::MAX - 2) * 2), product);
+    }
+
+    #[test]
+    fn returns_none_without_a_solution() {
+        assert_eq!(None, find_sum(&EXAMPLE, 1, 2));
+        assert_eq!(None, find_sum(&EXAMPLE, 1, 3));
+        assert_eq!(None, find_sum(&EXAMPLE, 1, 4));
+        assert_eq!(None, find_sum(&EXAMPLE, 2020, 7));
+        assert!(find_all_sums(&EXAMPLE, 1, 3).is_empty());
+        assert_eq!(0, count_sums(&EXAMPLE, 1, 3));
+    }
+}
@@ -0,0 +1,145 @@
+use common::{input, Error, Parsed, Solution};
+use regex::Captures;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RawBag {
+    pub name: String,
+    pub can_contain: HashMap<String, usize>,
+}
+
+impl RawBag {
+    pub fn contains_bag(&self, bag: &str) -> bool {
+        self.can_contain.contains_key(bag)
+    }
+}
+
+pub fn parse_inner_bags(line: &str) -> Result<HashMap<String, usize>, Error> {
+    if line.trim() == "no other bags." {
+        return Ok(HashMap::new());
+    }
+    let contained_bags_re = Regex::new(r"(?P<amount>\d+) (?P<name>[\w\s]*) bags?[,\.]").unwrap();
+    let contained_bags = contained_bags_re
+        .captures_iter(line)
+        .map(|captures: Captures| -> Result<(String, usize), Error> {
+            let amount = captures.name("amount").unwrap();
+            let amount_value: usize = amount
+                .as_str()
+                .parse::<usize>()
+                .map_err(|_| Error::parse_at(line, amount.start(), "could not parse bag amount"))?;
+            Ok((captures["name"].to_string(), amount_value))
+        })
+        .collect::<Result<HashMap<String, usize>, Error>>()?;
+    if contained_bags.is_empty() {
+        Err(Error::parse(
+            line,
+            1,
+            "expected `<amount> <name> bags` or `no other bags.`",
+        ))
+    } else {
+        Ok(contained_bags)
+    }
+}
+
+/// Parses a `<name> bags contain ...` rule.
+pub fn parse_bags_from_line(line: &str) -> Result<RawBag, Error> {
+    let bag_name_re = Regex::new(r"(?P<name>[\w\s]+?) bags contain (?P<rest>.*)").unwrap();
+
+    let initial_captures = bag_name_re
+        .captures(line)
+        .ok_or_else(|| Error::parse(line, 1, "expected `<name> bags contain ...`"))?;
+    let rest = initial_captures.name("rest").unwrap();
+    let rest_column = line[..rest.start()].chars().count();
+    let can_contain = parse_inner_bags(rest.as_str()).map_err(|error| match error {
+        Error::Parse { column, reason, .. } => Error::parse(line, column + rest_column, reason),
+        error => error,
+    })?;
+
+    Ok(RawBag {
+        name: initial_captures["name"].to_string(),
+        can_contain,
+    })
+}
+
+pub fn parse_bags(input: &str) -> Parsed<Vec<RawBag>> {
+    input::parse_lines(input, parse_bags_from_line)
+}
+
+/// Every bag that holds `target`, directly or inside another bag.
+pub fn can_eventually_contain(bags: &[RawBag], target: &str) -> HashSet<String> {
+    let mut containers: HashSet<String> = HashSet::new();
+    let mut to_visit: Vec<&str> = vec![target];
+    while let Some(name) = to_visit.pop() {
+        for bag in bags.iter().filter(|bag| bag.contains_bag(name)) {
+            if containers.insert(bag.name.clone()) {
+                to_visit.push(&bag.name);
+            }
+        }
+    }
+    containers
+}
+
+pub struct HandyHaversacks;
+
+impl Solution for HandyHaversacks {
+    const RECORDS: &'static str = "bag rules";
+
+    type Input = Vec<RawBag>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Parsed<Vec<RawBag>> {
+        parse_bags(input)
+    }
+
+    fn part_one(bags: &Vec<RawBag>) -> Option<usize> {
+        Some(can_eventually_contain(bags, "shiny gold").len())
+    }
+
+    fn part_two(_: &Vec<RawBag>) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_bags() {
+        let input = "1 bright white bag, 2 muted yellow bags.";
+        let result = parse_inner_bags(input);
+        let expected = vec![
+            ("bright white".to_string(), 1),
+            ("muted yellow".to_string(), 2),
+        ]
+        .into_iter()
+        .collect::<HashMap<String, usize>>();
+        assert_eq!(Ok(expected), result);
+    }
+
+    #[test]
+    fn can_parse_bag_without_contents() {
+        let expected = RawBag {
+            name: "faded blue".to_string(),
+            can_contain: HashMap::new(),
+        };
+        let result = parse_bags_from_line("faded blue bags contain no other bags.");
+        assert_eq!(Ok(expected), result);
+    }
+
+    #[test]
+    fn reports_unreadable_contents() {
+        let input = "faded blue bags contain some other bags.";
+        let expected = Error::Parse {
+            line: 1,
+            column: 25,
+            text: input.to_string(),
+            reason: "expected `<amount> <name> bags` or `no other bags.`".to_string(),
+        };
+        assert_eq!(Err(expected), parse_bags_from_line(input));
+    }
+}
@@ -0,0 +1,57 @@
+use common::{input, Parsed, Solution};
+use itertools::Itertools;
+use std::collections::HashSet;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+/// The answers of each person in a group, one set of questions per person.
+pub type Group = Vec<HashSet<char>>;
+
+pub struct CustomCustoms;
+
+impl Solution for CustomCustoms {
+    type Input = Vec<Group>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Parsed<Vec<Group>> {
+        let groups = input::paragraphs(input)
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|answer| answer.chars().collect::<HashSet<char>>())
+                    .collect()
+            })
+            .collect();
+        Parsed::new(groups)
+    }
+
+    fn part_one(groups: &Vec<Group>) -> Option<usize> {
+        Some(
+            groups
+                .iter()
+                .map(|answers| answers.iter().flatten().collect::<HashSet<&char>>().len())
+                .sum(),
+        )
+    }
+
+    fn part_two(groups: &Vec<Group>) -> Option<usize> {
+        Some(
+            groups
+                .iter()
+                .map(|answers| {
+                    answers
+                        .iter()
+                        .cloned()
+                        .fold1(|acc, answer| {
+                            acc.intersection(&answer)
+                                .copied()
+                                .collect::<HashSet<char>>()
+                        })
+                        .unwrap_or_default()
+                        .len()
+                })
+                .sum(),
+        )
+    }
+}
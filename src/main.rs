@@ -1,3 +1,110 @@
+use common::cli::{self, Args};
+use common::Part;
+use std::env;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Run {
+    day: u8,
+    args: Args,
+}
+
+fn parse_args(args: &[String]) -> Result<Run, String> {
+    match args.first().map(|command| command.as_str()) {
+        Some("run") => (),
+        Some(command) => return Err(format!("unknown command {}", command)),
+        None => return Err("no command given".to_string()),
+    }
+    let day = args
+        .get(1)
+        .ok_or("no day given")?
+        .parse::<u8>()
+        .map_err(|_| "day must be a number")?;
+    let args = Args::parse(&args[2..])?;
+    Ok(Run { day, args })
+}
+
+fn solve_day(run: &Run) -> Result<Vec<(Part, Option<String>)>, String> {
+    match run.day {
+        1 => cli::answers::<one::ReportRepair>(&run.args, one::DEFAULT_INPUT),
+        2 => cli::answers::<two::PasswordPhilosophy>(&run.args, two::DEFAULT_INPUT),
+        3 => cli::answers::<three::TobogganTrajectory>(&run.args, three::DEFAULT_INPUT),
+        4 => cli::answers::<four::PassportProcessing>(&run.args, four::DEFAULT_INPUT),
+        5 => cli::answers::<five::BinaryBoarding>(&run.args, five::DEFAULT_INPUT),
+        6 => cli::answers::<six::CustomCustoms>(&run.args, six::DEFAULT_INPUT),
+        7 => cli::answers::<seven::HandyHaversacks>(&run.args, seven::DEFAULT_INPUT),
+        8 => cli::answers::<eight::HandheldHalting>(&run.args, eight::DEFAULT_INPUT),
+        _ => Err(format!("day {} is not solved yet", run.day)),
+    }
+}
+
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let run = parse_args(&args).unwrap_or_else(|err| {
+        eprintln!("{}\nusage: aoc run <day> {}", err, cli::USAGE);
+        std::process::exit(1);
+    });
+    match solve_day(&run) {
+        Ok(answers) => {
+            for (part, answer) in answers {
+                match answer {
+                    Some(answer) => println!("day {} part {}: {}", run.day, part.number(), answer),
+                    None => println!("day {} part {}: no answer", run.day, part.number()),
+                }
+            }
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::cli::Mode;
+
+    fn args(str: &str) -> Vec<String> {
+        str.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn can_parse_run_with_defaults() {
+        let expected = Run {
+            day: 3,
+            args: Args {
+                parts: vec![Part::One, Part::Two],
+                mode: Mode::Lenient,
+                input: None,
+            },
+        };
+        assert_eq!(Ok(expected), parse_args(&args("run 3")));
+    }
+
+    #[test]
+    fn can_parse_run_with_part_and_input() {
+        let expected = Run {
+            day: 8,
+            args: Args {
+                parts: vec![Part::Two],
+                mode: Mode::Strict,
+                input: Some("input.txt".to_string()),
+            },
+        };
+        assert_eq!(
+            Ok(expected),
+            parse_args(&args("run 8 --part 2 --strict --input input.txt"))
+        );
+    }
+
+    #[test]
+    fn fails_on_unknown_part() {
+        assert!(parse_args(&args("run 1 --part 3")).is_err());
+    }
+
+    #[test]
+    fn fails_on_unsolved_day() {
+        let run = parse_args(&args("run 25")).unwrap();
+        assert!(solve_day(&run).is_err());
+    }
 }
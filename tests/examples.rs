@@ -0,0 +1,113 @@
+use common::Solution;
+
+#[test]
+fn day_one_example() {
+    let input = "1721\n979\n366\n299\n675\n1456";
+    let numbers = one::ReportRepair::parse(input).value;
+    assert_eq!(
+        Some(one::Product::Exact(514579)),
+        one::ReportRepair::part_one(&numbers)
+    );
+    assert_eq!(
+        Some(one::Product::Exact(241861950)),
+        one::ReportRepair::part_two(&numbers)
+    );
+}
+
+#[test]
+fn day_two_example() {
+    let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+    let entries = two::PasswordPhilosophy::parse(input).value;
+    assert_eq!(Some(2), two::PasswordPhilosophy::part_one(&entries));
+    assert_eq!(Some(1), two::PasswordPhilosophy::part_two(&entries));
+}
+
+#[test]
+fn day_three_example() {
+    let input = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+    let map = three::TobogganTrajectory::parse(input).value;
+    assert_eq!(Some(7), three::TobogganTrajectory::part_one(&map));
+    assert_eq!(Some(336), three::TobogganTrajectory::part_two(&map));
+}
+
+#[test]
+fn day_four_example() {
+    let input = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm";
+    let passports = four::PassportProcessing::parse(input).value;
+    assert_eq!(Some(3), four::PassportProcessing::part_one(&passports));
+    assert_eq!(Some(2), four::PassportProcessing::part_two(&passports));
+}
+
+#[test]
+fn day_four_part_one_example() {
+    let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+    let passports = four::PassportProcessing::parse(input).value;
+    assert_eq!(Some(2), four::PassportProcessing::part_one(&passports));
+}
+
+#[test]
+fn day_five_example() {
+    let input = "BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL";
+    let boarding_passes = five::BinaryBoarding::parse(input).value;
+    assert_eq!(Some(820), five::BinaryBoarding::part_one(&boarding_passes));
+}
+
+#[test]
+fn day_six_example() {
+    let input = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    let groups = six::CustomCustoms::parse(input).value;
+    assert_eq!(Some(11), six::CustomCustoms::part_one(&groups));
+    assert_eq!(Some(6), six::CustomCustoms::part_two(&groups));
+}
+
+#[test]
+fn day_seven_example() {
+    let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+    let bags = seven::HandyHaversacks::parse(input).value;
+    assert_eq!(Some(4), seven::HandyHaversacks::part_one(&bags));
+}
+
+#[test]
+fn day_eight_example() {
+    let input = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+    let program = eight::HandheldHalting::parse(input).value;
+    assert_eq!(Some(5), eight::HandheldHalting::part_one(&program));
+    assert_eq!(Some(8), eight::HandheldHalting::part_two(&program));
+}
@@ -0,0 +1,151 @@
+use common::grid::{Edges, Grid};
+use common::{Error, Parsed, Solution};
+
+pub mod render;
+pub mod route;
+pub mod search;
+pub mod terrain;
+pub mod trajectory;
+
+pub use search::{search_slopes, Extremes};
+pub use trajectory::{count_trees, trajectory, Slope, Trajectory};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+/// Counts the trees hit travelling `right` and `down` each step from the top left,
+/// until the toboggan passes the bottom of the map.
+pub fn count_tree_hits(map: &Grid<bool>, right: &usize, down: &usize) -> usize {
+    count_trees(map, Slope::new(*right as isize, *down as isize), None)
+}
+
+/// Parses the map of `#` trees and `.` open ground, which repeats to the right.
+pub fn parse_map(input: &str) -> Result<Grid<bool>, Error> {
+    Grid::parse(input).map(|map| map.with_edges(Edges::WrapHorizontally))
+}
+
+pub struct TobogganTrajectory;
+
+impl Solution for TobogganTrajectory {
+    const RECORDS: &'static str = "maps";
+
+    type Input = Grid<bool>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    /// A map that fails to parse is rejected whole, leaving an empty map that
+    /// neither part has an answer for.
+    fn parse(input: &str) -> Parsed<Grid<bool>> {
+        match parse_map(input) {
+            Ok(map) => Parsed::new(map),
+            Err(error) => Parsed {
+                value: Grid::default(),
+                rejected: vec![error],
+            },
+        }
+    }
+
+    fn part_one(map: &Grid<bool>) -> Option<usize> {
+        if map.is_empty() {
+            return None;
+        }
+        Some(count_tree_hits(map, &3, &1))
+    }
+
+    fn part_two(map: &Grid<bool>) -> Option<usize> {
+        if map.is_empty() {
+            return None;
+        }
+        let slopes: Vec<(usize, usize)> = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        slopes.iter().try_fold(1usize, |acc, (right, down)| {
+            acc.checked_mul(count_tree_hits(map, right, down))
+        })
+    }
+}
+
+/// The example map from the puzzle, shared by the tests of every module.
+#[cfg(test)]
+pub(crate) mod fixture {
+    use super::*;
+
+    pub const EXAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    pub fn example() -> Grid<bool> {
+        parse_map(EXAMPLE).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn can_validate_updated_is_correct() {
+        assert_eq!(true, true);
+    }
+
+    /// A map of `height` rows where the cell at `(x, y)` is a tree when `tree` says so.
+    fn generate(width: usize, height: usize, tree: impl Fn(usize, usize) -> bool) -> Grid<bool> {
+        let rows = (0..height)
+            .map(|y| (0..width).map(|x| tree(x, y)).collect())
+            .collect();
+        Grid::from_rows(rows)
+            .unwrap()
+            .with_edges(Edges::WrapHorizontally)
+    }
+
+    #[test]
+    fn a_rejected_map_has_no_answers() {
+        let map = TobogganTrajectory::parse("..#\n.Z.").value;
+        assert_eq!(None, TobogganTrajectory::part_one(&map));
+        assert_eq!(None, TobogganTrajectory::part_two(&map));
+    }
+
+    #[test]
+    fn counts_more_than_255_trees() {
+        let forest = generate(31, 5000, |_, _| true);
+        assert_eq!(5000, count_tree_hits(&forest, &3, &1));
+        assert_eq!(2500, count_tree_hits(&forest, &1, &2));
+        assert_eq!(Some(5000), TobogganTrajectory::part_one(&forest));
+        assert_eq!(
+            Some(5000usize.pow(4) * 2500),
+            TobogganTrajectory::part_two(&forest)
+        );
+    }
+
+    #[test]
+    fn matches_a_direct_count_on_a_generated_map() {
+        // a fixed scattering of trees, so the map is the same every run
+        let tree = |x: usize, y: usize| (x * 7919 + y * 104_729 + x * y * 31) % 11 < 4;
+        let map = generate(31, 12_000, tree);
+        for &(right, down) in &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2), (40, 3)] {
+            let expected = (0..12_000)
+                .step_by(down)
+                .enumerate()
+                .filter(|&(step, y)| tree(step * right % 31, y))
+                .count();
+            assert!(expected > 255);
+            assert_eq!(expected, count_tree_hits(&map, &right, &down));
+        }
+    }
+
+    #[test]
+    fn part_two_reports_no_answer_when_the_product_overflows() {
+        let forest = generate(1, 20_000, |_, _| true);
+        let product = 20_000u128.pow(4) * 10_000;
+        assert_eq!(
+            usize::try_from(product).ok(),
+            TobogganTrajectory::part_two(&forest)
+        );
+    }
+}
@@ -1,51 +1,204 @@
-#[cfg(windows)]
-const LINE_ENDING: &'static str = "\r\n";
-#[cfg(not(windows))]
-const LINE_ENDING: &'static str = "\n";
-
-fn count_tree_hits(map: &Vec<Vec<u8>>, right: &usize, down: &usize) -> u8 {
-    let (result, _) = map
-        .into_iter()
-        .step_by(*down)
-        .fold((0, 0), |(acc, pos), line| {
-            let wrapped_pos = pos % line.len();
-            let value = line.get(wrapped_pos).unwrap_or(&0);
-            (acc + value, pos + right)
-        });
-    result
+use common::cli::{self, Args};
+use common::grid::Edges;
+use common::input;
+use std::env;
+use std::fs;
+use std::process;
+use three::render;
+use three::route::{self, DOWNHILL};
+use three::search::parse_range;
+use three::terrain::{self, Legend};
+use three::{search_slopes, trajectory, Slope, TobogganTrajectory, DEFAULT_INPUT};
+
+const USAGE: &str = "usage: three [--slope DX,DY [--start X,Y] [--visited]]
+       three --search [--dx A..B] [--dy A..B]
+       three --legend C=NAME:COST,... [--slope DX,DY] [--start X,Y]
+       three --route [--move DX,DY]... [--column X] [--bounded]
+       three --render [--slope DX,DY] [--start X,Y] [--colour] [--svg PATH] [--ppm PATH] [--scale N]
+       three";
+
+fn parse_start(str: &str) -> Result<(isize, isize), String> {
+    let mut parts = str.splitn(2, ',').map(|part| part.trim().parse::<isize>());
+    match (parts.next(), parts.next()) {
+        (Some(Ok(x)), Some(Ok(y))) => Ok((x, y)),
+        _ => Err(format!("expected a start as x,y, got {}", str)),
+    }
+}
+
+fn list(slopes: &[Slope]) -> String {
+    slopes
+        .iter()
+        .map(|slope| format!("({})", slope))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn search(mut args: Vec<String>) -> Result<(), String> {
+    let dx = cli::take_option(&mut args, "--dx")?.unwrap_or_else(|| "-20..=20".to_string());
+    let dy = cli::take_option(&mut args, "--dy")?.unwrap_or_else(|| "1..=10".to_string());
+    let (dxs, dys) = (parse_range(&dx)?, parse_range(&dy)?);
+
+    let args = Args::parse(&args)?;
+    let map = cli::load::<TobogganTrajectory>(&args, DEFAULT_INPUT)?;
+    match search_slopes(&map, dxs, dys) {
+        Some(extremes) => {
+            println!(
+                "fewest: {} trees with {}",
+                extremes.fewest,
+                list(&extremes.fewest_slopes)
+            );
+            println!(
+                "most: {} trees with {}",
+                extremes.most,
+                list(&extremes.most_slopes)
+            );
+        }
+        None => println!("no slopes in dx {} and dy {}", dx, dy),
+    }
+    Ok(())
+}
+
+fn render(mut args: Vec<String>) -> Result<(), String> {
+    let slope = cli::take_option(&mut args, "--slope")?;
+    let slope = slope.as_deref().unwrap_or("3,1").parse::<Slope>()?;
+    let start = cli::take_option(&mut args, "--start")?;
+    let start = start.as_deref().map(parse_start).transpose()?;
+    let colour = cli::take_flag(&mut args, "--colour");
+    let svg = cli::take_option(&mut args, "--svg")?;
+    let ppm = cli::take_option(&mut args, "--ppm")?;
+    let scale = match cli::take_option(&mut args, "--scale")? {
+        Some(scale) => scale
+            .parse::<usize>()
+            .ok()
+            .filter(|&scale| scale > 0)
+            .ok_or_else(|| format!("--scale must be a positive number, got {}", scale))?,
+        None => 8,
+    };
+
+    let args = Args::parse(&args)?;
+    let map = cli::load::<TobogganTrajectory>(&args, DEFAULT_INPUT)?;
+    let annotated = render::annotate(&map, slope, start);
+    if colour {
+        println!("{}", render::to_ansi(&annotated));
+    } else {
+        println!("{}", annotated);
+    }
+    if let Some(path) = svg {
+        fs::write(&path, render::to_svg(&annotated, scale))
+            .map_err(|err| format!("could not write {}: {}", path, err))?;
+    }
+    if let Some(path) = ppm {
+        fs::write(&path, render::to_ppm(&annotated, scale))
+            .map_err(|err| format!("could not write {}: {}", path, err))?;
+    }
+    Ok(())
+}
+
+fn weigh(spec: &str, mut args: Vec<String>) -> Result<(), String> {
+    let legend = Legend::parse(spec)?;
+    let slope = cli::take_option(&mut args, "--slope")?;
+    let slope = slope.as_deref().unwrap_or("3,1").parse::<Slope>()?;
+    let start = cli::take_option(&mut args, "--start")?;
+    let start = start.as_deref().map(parse_start).transpose()?;
+
+    let args = Args::parse(&args)?;
+    let map = input::read(args.input.as_deref(), DEFAULT_INPUT)
+        .and_then(|input| legend.read_map(&input))
+        .map_err(|err| err.to_string())?;
+    let cost = terrain::weighted_cost(&map, &legend, slope, start);
+    println!("slope {} costs {}", slope, cost.total);
+    for (name, (count, total)) in cost.by_terrain {
+        println!("{}: {} cells costing {}", name, count, total);
+    }
+    Ok(())
+}
+
+fn find_route(mut args: Vec<String>) -> Result<(), String> {
+    let mut moves = Vec::new();
+    while let Some(step) = cli::take_option(&mut args, "--move")? {
+        moves.push(step.parse::<Slope>()?);
+    }
+    if moves.is_empty() {
+        moves.extend_from_slice(&DOWNHILL);
+    }
+    let column = match cli::take_option(&mut args, "--column")? {
+        Some(column) => Some(
+            column
+                .parse::<usize>()
+                .map_err(|_| format!("--column must be a column number, got {}", column))?,
+        ),
+        None => None,
+    };
+    let bounded = cli::take_flag(&mut args, "--bounded");
+
+    let args = Args::parse(&args)?;
+    let mut map = cli::load::<TobogganTrajectory>(&args, DEFAULT_INPUT)?;
+    if bounded {
+        map = map.with_edges(Edges::Bounded);
+    }
+    match route::fewest_trees(&map, &moves, column) {
+        Some(route) => {
+            println!(
+                "route hits {} trees over {} cells",
+                route.cost,
+                route.cells.len()
+            );
+            for (x, y) in route.cells {
+                println!("{},{}", x, y);
+            }
+        }
+        None => println!("no route reaches the bottom with those moves"),
+    }
+    Ok(())
+}
+
+fn run(mut args: Vec<String>) -> Result<(), String> {
+    if cli::take_flag(&mut args, "--route") {
+        return find_route(args);
+    }
+    if cli::take_flag(&mut args, "--search") {
+        return search(args);
+    }
+    if let Some(spec) = cli::take_option(&mut args, "--legend")? {
+        return weigh(&spec, args);
+    }
+    if cli::take_flag(&mut args, "--render") {
+        return render(args);
+    }
+    let slope = cli::take_option(&mut args, "--slope")?;
+    let start = cli::take_option(&mut args, "--start")?;
+    let visited = cli::take_flag(&mut args, "--visited");
+    let slope = match slope {
+        Some(slope) => slope.parse::<Slope>()?,
+        None if start.is_none() && !visited => {
+            cli::run::<TobogganTrajectory>(&args, DEFAULT_INPUT);
+            return Ok(());
+        }
+        None => return Err("--start and --visited need a --slope".to_string()),
+    };
+    let start = start.as_deref().map(parse_start).transpose()?;
+
+    let args = Args::parse(&args)?;
+    let map = cli::load::<TobogganTrajectory>(&args, DEFAULT_INPUT)?;
+    let result = trajectory(&map, slope, start);
+    println!(
+        "slope {} hits {} trees over {} cells",
+        slope,
+        result.trees,
+        result.visited.len()
+    );
+    if visited {
+        for (x, y) in result.visited {
+            println!("{},{}", x, y);
+        }
+    }
+    Ok(())
 }
 
 fn main() {
-    let input = include_str!("input.txt");
-    let map = input
-        .split(LINE_ENDING)
-        .filter(|line| !line.is_empty())
-        .map(|line: &str| {
-            line.chars()
-                .map(|character: char| match character {
-                    '#' => Ok(1),
-                    '.' => Ok(0),
-                    _ => Err("cannot understand character"),
-                })
-                .filter_map(Result::ok)
-                .collect::<Vec<u8>>()
-        })
-        .collect::<Vec<Vec<u8>>>();
-    let part_one_trees_hit = count_tree_hits(&map, &3, &1);
-    let slopes: Vec<(usize, usize)> = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-    let part_two_trees_hit = slopes.iter().fold(1, |acc, (right, down)| {
-        acc * usize::from(count_tree_hits(&map, right, down))
-    });
-    println!("part one we would hit {}", part_one_trees_hit);
-    println!("part two we would hit {}", part_two_trees_hit);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn can_validate_updated_is_correct() {
-        assert_eq!(true, true);
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(args) {
+        eprintln!("{}\n{} {}", err, USAGE, cli::USAGE);
+        process::exit(1);
     }
 }
@@ -0,0 +1,237 @@
+use crate::trajectory::{path, Slope};
+use common::grid::{Cell, Grid};
+
+/// A cell of the map once a toboggan has been down it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Mark {
+    Open,
+    Tree,
+    /// Open ground the toboggan stopped on.
+    Passed,
+    /// A tree the toboggan hit.
+    Hit,
+}
+
+/// The characters the puzzle uses: `O` where the toboggan passed over open
+/// ground and `X` where it hit a tree.
+impl Cell for Mark {
+    fn from_char(character: char) -> Option<Mark> {
+        match character {
+            '.' => Some(Mark::Open),
+            '#' => Some(Mark::Tree),
+            'O' => Some(Mark::Passed),
+            'X' => Some(Mark::Hit),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Mark::Open => '.',
+            Mark::Tree => '#',
+            Mark::Passed => 'O',
+            Mark::Hit => 'X',
+        }
+    }
+}
+
+impl Mark {
+    fn ansi(&self) -> &'static str {
+        match self {
+            Mark::Open => "\x1b[2m",
+            Mark::Tree => "\x1b[32m",
+            Mark::Passed => "\x1b[1;36m",
+            Mark::Hit => "\x1b[1;31m",
+        }
+    }
+
+    fn rgb(&self) -> [u8; 3] {
+        match self {
+            Mark::Open => [240, 240, 240],
+            Mark::Tree => [34, 120, 50],
+            Mark::Passed => [40, 150, 220],
+            Mark::Hit => [220, 40, 40],
+        }
+    }
+}
+
+/// Marks every cell the toboggan visits following `slope` from `start`.
+///
+/// The map is repeated side by side once for each time the toboggan wraps
+/// into it, so the path is drawn as one unbroken line. Copies the toboggan
+/// jumps straight over are left out.
+pub fn annotate(map: &Grid<bool>, slope: Slope, start: Option<(isize, isize)>) -> Grid<Mark> {
+    let width = map.width().max(1) as isize;
+    let (start_x, _) = start.unwrap_or((0, 0));
+    // track the column as if the map never wrapped to tell its copies apart
+    let visits = path(map, slope, start)
+        .scan(Some(start_x), |unwrapped, (x, y)| {
+            let tile = unwrapped.as_ref()?.div_euclid(width);
+            *unwrapped = unwrapped.and_then(|column| column.checked_add(slope.dx));
+            Some((tile, x, y))
+        })
+        .collect::<Vec<(isize, usize, usize)>>();
+    let mut tiles = visits
+        .iter()
+        .map(|&(tile, _, _)| tile)
+        .collect::<Vec<isize>>();
+    tiles.sort_unstable();
+    tiles.dedup();
+    if tiles.is_empty() {
+        tiles.push(0);
+    }
+    let rows = map
+        .rows()
+        .map(|row| {
+            tiles
+                .iter()
+                .flat_map(|_| row.iter())
+                .map(|&tree| if tree { Mark::Tree } else { Mark::Open })
+                .collect::<Vec<Mark>>()
+        })
+        .collect::<Vec<Vec<Mark>>>();
+    let mut annotated =
+        Grid::from_rows(rows).expect("every row is repeated the same number of times");
+    for (tile, x, y) in visits {
+        let copy = tiles.binary_search(&tile).unwrap_or_default() as isize;
+        let column = copy * width + x as isize;
+        if let Some(cell) = annotated.get_mut(column, y as isize) {
+            *cell = match cell {
+                Mark::Tree | Mark::Hit => Mark::Hit,
+                Mark::Open | Mark::Passed => Mark::Passed,
+            };
+        }
+    }
+    annotated
+}
+
+/// The annotated map as text, coloured for a terminal.
+pub fn to_ansi(annotated: &Grid<Mark>) -> String {
+    annotated
+        .rows()
+        .map(|row| {
+            let mut line = String::new();
+            for mark in row {
+                line.push_str(mark.ansi());
+                line.push(mark.to_char());
+                line.push_str("\x1b[0m");
+            }
+            line
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The annotated map as an SVG image, with each cell `scale` pixels square.
+pub fn to_svg(annotated: &Grid<Mark>, scale: usize) -> String {
+    let (width, height) = (annotated.width() * scale, annotated.height() * scale);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        width, height, width, height
+    );
+    let [r, g, b] = Mark::Open.rgb();
+    svg.push_str(&format!(
+        "<rect width=\"{}\" height=\"{}\" fill=\"rgb({},{},{})\"/>\n",
+        width, height, r, g, b
+    ));
+    for ((x, y), mark) in annotated.iter().filter(|(_, &mark)| mark != Mark::Open) {
+        let [r, g, b] = mark.rgb();
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"rgb({},{},{})\"/>\n",
+            x * scale,
+            y * scale,
+            scale,
+            scale,
+            r,
+            g,
+            b
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The annotated map as a binary PPM image, with each cell `scale` pixels square.
+pub fn to_ppm(annotated: &Grid<Mark>, scale: usize) -> Vec<u8> {
+    let (width, height) = (annotated.width() * scale, annotated.height() * scale);
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    for row in annotated.rows().take(annotated.height()) {
+        let line = row
+            .iter()
+            .flat_map(|mark| std::iter::repeat_n(mark.rgb(), scale))
+            .flatten()
+            .collect::<Vec<u8>>();
+        for _ in 0..scale {
+            ppm.extend_from_slice(&line);
+        }
+    }
+    ppm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::example;
+
+    #[test]
+    fn marks_the_path_as_in_the_puzzle() {
+        let annotated = annotate(&example(), Slope::new(3, 1), None);
+        let expected = "\
+O.##.........##.........##.......
+#..O#...#..#...#...#..#...#...#..
+.#....X..#..#....#..#..#....#..#.
+..#.#...#O#..#.#...#.#..#.#...#.#
+.#...##..#..X...##..#..#...##..#.
+..#.##.......#.X#.......#.##.....
+.#.#.#....#.#.#.#.O..#.#.#.#....#
+.#........#.#........X.#........#
+#.##...#...#.##...#...#.X#...#...
+#...##....##...##....##...#X....#
+.#..#...#.#.#..#...#.#.#..#...X.#";
+        assert_eq!(expected, annotated.to_string());
+        assert_eq!(
+            7,
+            annotated
+                .iter()
+                .filter(|(_, &mark)| mark == Mark::Hit)
+                .count()
+        );
+    }
+
+    #[test]
+    fn extends_the_map_to_the_left_for_leftward_slopes() {
+        let annotated = annotate(&example(), Slope::new(-1, 1), None);
+        assert_eq!(22, annotated.width());
+        assert_eq!(Some(&Mark::Passed), annotated.get(11, 0));
+        assert_eq!(Some(&Mark::Passed), annotated.get(10, 1));
+        assert_eq!(Some(&Mark::Hit), annotated.get(1, 10));
+    }
+
+    #[test]
+    fn leaves_out_copies_a_huge_slope_jumps_over() {
+        let slope = Slope::new(4611686018427387905, 1);
+        let annotated = annotate(&example(), slope, Some((-4611686018427387904, 0)));
+        assert_eq!(33, annotated.width());
+        assert_eq!(Some(&Mark::Passed), annotated.get(7, 0));
+        assert_eq!(Some(&Mark::Passed), annotated.get(12, 1));
+        assert_eq!(Some(&Mark::Hit), annotated.get(28, 2));
+    }
+
+    #[test]
+    fn can_export_images() {
+        let annotated = annotate(&example(), Slope::new(1, 1), None);
+        let svg = to_svg(&annotated, 4);
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"44\" height=\"44\"")
+        );
+        let ppm = to_ppm(&annotated, 2);
+        let header = b"P6\n22 22\n255\n";
+        assert_eq!(&header[..], &ppm[..header.len()]);
+        assert_eq!(header.len() + 22 * 22 * 3, ppm.len());
+        assert_eq!(
+            &Mark::Passed.rgb()[..],
+            &ppm[header.len()..header.len() + 3]
+        );
+        assert!(to_ansi(&annotated).contains("\x1b[1;31mX\x1b[0m"));
+    }
+}
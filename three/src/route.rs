@@ -0,0 +1,141 @@
+use crate::trajectory::Slope;
+use common::grid::Grid;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::convert::TryFrom;
+
+/// Straight down, or one column either way on the way down.
+pub const DOWNHILL: [Slope; 3] = [
+    Slope { dx: 0, dy: 1 },
+    Slope { dx: -1, dy: 1 },
+    Slope { dx: 1, dy: 1 },
+];
+
+/// The cheapest way found from the top row to the bottom row.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Route {
+    pub cost: u64,
+    /// Every cell on the route from top to bottom, the first and last included.
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// Finds the cheapest route from the top row to the bottom row taking only
+/// `moves`, where entering a cell costs `cost` of it, the first cell included.
+///
+/// The route starts in column `start` or, without one, wherever on the top
+/// row is cheapest. Whether moves, and a start past the last column, wrap
+/// around the sides is up to the map's edges. Costs are never negative, so
+/// this is Dijkstra's algorithm.
+pub fn cheapest_route<T, F>(
+    map: &Grid<T>,
+    moves: &[Slope],
+    start: Option<usize>,
+    cost: F,
+) -> Option<Route>
+where
+    F: Fn(&T) -> u64,
+{
+    let (width, height) = (map.width(), map.height());
+    let index = |(x, y): (usize, usize)| y * width + x;
+    let mut best = vec![u64::MAX; width * height];
+    let mut previous: Vec<Option<(usize, usize)>> = vec![None; width * height];
+    let mut queue = BinaryHeap::new();
+    let starts = match start {
+        Some(x) => vec![isize::try_from(x).ok().and_then(|x| map.locate(x, 0))],
+        None => (0..width).map(|x| map.locate(x as isize, 0)).collect(),
+    };
+    for (x, y) in starts.into_iter().flatten() {
+        if let Some(cell) = map.get(x as isize, y as isize) {
+            best[index((x, y))] = cost(cell);
+            queue.push(Reverse((cost(cell), x, y)));
+        }
+    }
+    while let Some(Reverse((spent, x, y))) = queue.pop() {
+        if spent > best[index((x, y))] {
+            continue;
+        }
+        if y + 1 == height {
+            let mut cells = vec![(x, y)];
+            while let Some(cell) = previous[index(cells[cells.len() - 1])] {
+                cells.push(cell);
+            }
+            cells.reverse();
+            return Some(Route { cost: spent, cells });
+        }
+        for step in moves {
+            let (to_x, to_y) = (x as isize + step.dx, y as isize + step.dy);
+            let (next, cell) = match (map.locate(to_x, to_y), map.get(to_x, to_y)) {
+                (Some(next), Some(cell)) => (next, cell),
+                _ => continue,
+            };
+            let total = spent.saturating_add(cost(cell));
+            if total < best[index(next)] {
+                best[index(next)] = total;
+                previous[index(next)] = Some((x, y));
+                queue.push(Reverse((total, next.0, next.1)));
+            }
+        }
+    }
+    None
+}
+
+/// The route hitting the fewest trees, with each tree costing one.
+pub fn fewest_trees(map: &Grid<bool>, moves: &[Slope], start: Option<usize>) -> Option<Route> {
+    cheapest_route(map, moves, start, |&tree| u64::from(tree))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::grid::Edges;
+
+    fn map(input: &str, edges: Edges) -> Grid<bool> {
+        input.parse::<Grid<bool>>().unwrap().with_edges(edges)
+    }
+
+    #[test]
+    fn can_weave_between_trees() {
+        let map = map("#.#\n.#.\n#.#\n.#.", Edges::Bounded);
+        let route = fewest_trees(&map, &DOWNHILL, None).unwrap();
+        assert_eq!(0, route.cost);
+        assert_eq!(vec![(1, 0), (0, 1), (1, 2), (0, 3)], route.cells);
+        let straight = fewest_trees(&map, &DOWNHILL[..1], Some(0)).unwrap();
+        assert_eq!(2, straight.cost);
+    }
+
+    #[test]
+    fn wraps_around_the_sides_when_the_map_does() {
+        let input = ".##\n##.\n.##";
+        let bounded = fewest_trees(&map(input, Edges::Bounded), &DOWNHILL, Some(0));
+        assert_eq!(1, bounded.unwrap().cost);
+        let wrapped = fewest_trees(&map(input, Edges::WrapHorizontally), &DOWNHILL, Some(0));
+        let wrapped = wrapped.unwrap();
+        assert_eq!(0, wrapped.cost);
+        assert_eq!(vec![(0, 0), (2, 1), (0, 2)], wrapped.cells);
+    }
+
+    #[test]
+    fn can_use_any_moves_and_costs() {
+        let map = ".#.\n.#.\n...".parse::<Grid<bool>>().unwrap();
+        let sideways = [Slope::new(0, 1), Slope::new(1, 0), Slope::new(0, -1)];
+        let route = cheapest_route(&map, &sideways, Some(2), |&tree| if tree { 10 } else { 1 });
+        assert_eq!(Some(3), route.map(|route| route.cost));
+        assert_eq!(None, fewest_trees(&map, &[Slope::new(1, 0)], None));
+    }
+
+    #[test]
+    fn starts_past_the_last_column_wrap_or_go_nowhere() {
+        let wrapped = map(".#\n#.", Edges::WrapHorizontally);
+        let route = fewest_trees(&wrapped, &DOWNHILL[..1], Some(3)).unwrap();
+        assert_eq!(1, route.cost);
+        assert_eq!(vec![(1, 0), (1, 1)], route.cells);
+        let row = map("...", Edges::WrapHorizontally);
+        assert_eq!(
+            Some(vec![(2, 0)]),
+            fewest_trees(&row, &DOWNHILL, Some(5)).map(|route| route.cells)
+        );
+        let bounded = map(".#\n#.", Edges::Bounded);
+        assert_eq!(None, fewest_trees(&bounded, &DOWNHILL, Some(3)));
+        assert_eq!(None, fewest_trees(&wrapped, &DOWNHILL, Some(usize::MAX)));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::trajectory::{count_trees, Slope};
+use common::grid::{Edges, Grid};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// The slopes that hit the fewest and the most trees, with ties kept.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Extremes {
+    pub fewest: usize,
+    pub fewest_slopes: Vec<Slope>,
+    pub most: usize,
+    pub most_slopes: Vec<Slope>,
+}
+
+/// Tries every slope from the top left with `dx` and `dy` in the given ranges.
+///
+/// On a map that wraps horizontally, moving `dx` or `dx + width` columns lands
+/// on the same cells, so each slope is only followed once per column offset.
+pub fn search_slopes(
+    map: &Grid<bool>,
+    dxs: RangeInclusive<isize>,
+    dys: RangeInclusive<isize>,
+) -> Option<Extremes> {
+    let width = map.width().max(1) as isize;
+    let wraps = matches!(map.edges(), Edges::WrapHorizontally | Edges::Wrap);
+    let mut counted: HashMap<Slope, usize> = HashMap::new();
+    let mut extremes: Option<Extremes> = None;
+    for dy in dys {
+        for dx in dxs.clone() {
+            let slope = Slope::new(dx, dy);
+            let key = if wraps {
+                Slope::new(dx.rem_euclid(width), dy)
+            } else {
+                slope
+            };
+            let trees = *counted
+                .entry(key)
+                .or_insert_with(|| count_trees(map, key, None));
+            let found = extremes.get_or_insert_with(|| Extremes {
+                fewest: trees,
+                fewest_slopes: Vec::new(),
+                most: trees,
+                most_slopes: Vec::new(),
+            });
+            if trees < found.fewest {
+                found.fewest = trees;
+                found.fewest_slopes.clear();
+            }
+            if trees == found.fewest {
+                found.fewest_slopes.push(slope);
+            }
+            if trees > found.most {
+                found.most = trees;
+                found.most_slopes.clear();
+            }
+            if trees == found.most {
+                found.most_slopes.push(slope);
+            }
+        }
+    }
+    extremes
+}
+
+/// Reads a range written `a..b`, excluding `b`, or `a..=b`, including it.
+pub fn parse_range(str: &str) -> Result<RangeInclusive<isize>, String> {
+    let invalid = || format!("expected a range like -20..20 or 1..=10, got {}", str);
+    let (start, end, inclusive) = match str.find("..=") {
+        Some(index) => (&str[..index], &str[index + 3..], true),
+        None => {
+            let index = str.find("..").ok_or_else(invalid)?;
+            (&str[..index], &str[index + 2..], false)
+        }
+    };
+    let start = start.trim().parse::<isize>().map_err(|_| invalid())?;
+    let end = end.trim().parse::<isize>().map_err(|_| invalid())?;
+    let end = if inclusive {
+        end
+    } else {
+        end.checked_sub(1).ok_or_else(invalid)?
+    };
+    Ok(start..=end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::example;
+    use crate::trajectory::Slope;
+
+    #[test]
+    fn finds_the_same_extremes_as_checking_every_slope() {
+        let map = example();
+        let result = search_slopes(&map, -20..=20, 1..=10).unwrap();
+        let mut counts = Vec::new();
+        for dy in 1..=10 {
+            for dx in -20..=20 {
+                counts.push(count_trees(&map, Slope::new(dx, dy), None));
+            }
+        }
+        assert_eq!(*counts.iter().min().unwrap(), result.fewest);
+        assert_eq!(*counts.iter().max().unwrap(), result.most);
+        for slope in &result.most_slopes {
+            assert_eq!(result.most, count_trees(&map, *slope, None));
+        }
+    }
+
+    #[test]
+    fn keeps_every_tied_slope() {
+        let map = example();
+        let result = search_slopes(&map, 3..=3, 1..=1).unwrap();
+        assert_eq!(7, result.fewest);
+        assert_eq!(vec![Slope::new(3, 1)], result.most_slopes);
+        let congruent = search_slopes(&map, 14..=14, 1..=1).unwrap();
+        assert_eq!(7, congruent.most);
+        let both = search_slopes(&map, -8..=3, 1..=1).unwrap();
+        assert!(
+            both.most_slopes.contains(&Slope::new(-8, 1))
+                == both.most_slopes.contains(&Slope::new(3, 1))
+        );
+        assert_eq!(
+            None,
+            search_slopes(&map, parse_range("1..1").unwrap(), 1..=1)
+        );
+    }
+
+    #[test]
+    fn can_parse_ranges() {
+        assert_eq!(Ok(-20..=19), parse_range("-20..20"));
+        assert_eq!(Ok(1..=10), parse_range("1..=10"));
+        assert!(parse_range("1-10").is_err());
+        assert!(parse_range("0..-9223372036854775808").is_err());
+    }
+}
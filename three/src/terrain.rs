@@ -0,0 +1,155 @@
+use crate::trajectory::{path, Slope};
+use common::grid::{Edges, Grid};
+use common::Error;
+use std::collections::{BTreeMap, HashMap};
+
+/// A kind of ground on the map and what crossing it costs.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Terrain {
+    pub name: String,
+    pub cost: u64,
+}
+
+impl Terrain {
+    pub fn new(name: &str, cost: u64) -> Terrain {
+        Terrain {
+            name: name.to_string(),
+            cost,
+        }
+    }
+}
+
+/// Which character on the map stands for which terrain.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Legend {
+    terrains: HashMap<char, Terrain>,
+}
+
+impl Legend {
+    /// The puzzle's own map: `#` is a tree costing 1 and `.` is open ground
+    /// costing nothing, so the cost of a slope is its number of tree hits.
+    pub fn standard() -> Legend {
+        Legend::default()
+            .with('#', Terrain::new("tree", 1))
+            .with('.', Terrain::new("open", 0))
+    }
+
+    pub fn with(mut self, character: char, terrain: Terrain) -> Legend {
+        self.terrains.insert(character, terrain);
+        self
+    }
+
+    pub fn get(&self, character: char) -> Option<&Terrain> {
+        self.terrains.get(&character)
+    }
+
+    /// Reads a legend written as `C=name:cost` entries separated by commas,
+    /// such as `#=tree:1,.=open:0,R=rock:5`, on top of the standard one.
+    pub fn parse(spec: &str) -> Result<Legend, String> {
+        let mut legend = Legend::standard();
+        for entry in spec.split(',').filter(|entry| !entry.is_empty()) {
+            let invalid = || format!("expected a legend entry as C=name:cost, got {}", entry);
+            let mut characters = entry.chars();
+            let character = characters.next().ok_or_else(invalid)?;
+            let rest = characters.as_str().strip_prefix('=').ok_or_else(invalid)?;
+            let mut parts = rest.splitn(2, ':');
+            let name = parts
+                .next()
+                .filter(|name| !name.is_empty())
+                .ok_or_else(invalid)?;
+            let cost = parts
+                .next()
+                .and_then(|cost| cost.parse::<u64>().ok())
+                .ok_or_else(invalid)?;
+            legend = legend.with(character, Terrain::new(name, cost));
+        }
+        Ok(legend)
+    }
+
+    /// Reads a map whose characters all have to be in the legend, reporting
+    /// where any other character is. The map repeats to the right.
+    pub fn read_map(&self, input: &str) -> Result<Grid<char>, Error> {
+        Grid::parse_with(input, |character| match self.get(character) {
+            Some(_) => Ok(character),
+            None => Err(format!("{:?} is not in the terrain legend", character)),
+        })
+        .map(|map| map.with_edges(Edges::WrapHorizontally))
+    }
+}
+
+/// What a slope crossed and what it cost.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Cost {
+    pub total: u64,
+    /// How many cells of each terrain were crossed and what they cost together.
+    pub by_terrain: BTreeMap<String, (usize, u64)>,
+}
+
+/// Adds up the cost of every cell visited following `slope` from `start`,
+/// stopping at `u64::MAX` rather than overflowing.
+pub fn weighted_cost(
+    map: &Grid<char>,
+    legend: &Legend,
+    slope: Slope,
+    start: Option<(isize, isize)>,
+) -> Cost {
+    let mut cost = Cost::default();
+    for (x, y) in path(map, slope, start) {
+        let character = map.get(x as isize, y as isize).copied();
+        if let Some(terrain) = character.and_then(|character| legend.get(character)) {
+            cost.total = cost.total.saturating_add(terrain.cost);
+            let (count, total) = cost.by_terrain.entry(terrain.name.clone()).or_default();
+            *count += 1;
+            *total = total.saturating_add(terrain.cost);
+        }
+    }
+    cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trajectory::count_trees;
+
+    #[test]
+    fn standard_legend_costs_one_per_tree() {
+        let input = "..##.......\n#...#...#..\n.#....#..#.\n..#.#...#.#";
+        let legend = Legend::standard();
+        let map = legend.read_map(input).unwrap();
+        let trees = crate::parse_map(input).unwrap();
+        for slope in &[Slope::new(1, 1), Slope::new(3, 1), Slope::new(1, 2)] {
+            let cost = weighted_cost(&map, &legend, *slope, None);
+            assert_eq!(count_trees(&trees, *slope, None) as u64, cost.total);
+        }
+    }
+
+    #[test]
+    fn can_weigh_other_terrain() {
+        let legend = Legend::parse("R=rock:5,~=ice:2,*=snow:3").unwrap();
+        let map = legend.read_map("R.#\n.~*\n#.*").unwrap();
+        let cost = weighted_cost(&map, &legend, Slope::new(1, 1), None);
+        assert_eq!(5 + 2 + 3, cost.total);
+        assert_eq!(Some(&(1, 2)), cost.by_terrain.get("ice"));
+        assert_eq!(None, cost.by_terrain.get("tree"));
+    }
+
+    #[test]
+    fn costs_too_large_to_add_stop_at_the_largest() {
+        let legend = Legend::parse("R=rock:18446744073709551615").unwrap();
+        let map = legend.read_map("R..\n.R.").unwrap();
+        let cost = weighted_cost(&map, &legend, Slope::new(1, 1), None);
+        assert_eq!(u64::MAX, cost.total);
+        assert_eq!(Some(&(2, u64::MAX)), cost.by_terrain.get("rock"));
+    }
+
+    #[test]
+    fn reports_characters_missing_from_the_legend() {
+        let error = Legend::standard().read_map("..#\n.R.").unwrap_err();
+        assert_eq!(
+            "line 2, column 2: 'R' is not in the terrain legend in \".R.\"",
+            error.to_string()
+        );
+        assert!(Legend::parse("R=rock").is_err());
+        assert!(Legend::parse("Rrock:1").is_err());
+    }
+}
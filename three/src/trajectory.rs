@@ -0,0 +1,245 @@
+use common::grid::Grid;
+use std::fmt;
+use std::str::FromStr;
+
+/// How far the toboggan moves each step: `dx` columns to the right and `dy`
+/// rows down, with negative values going left or up.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct Slope {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Slope {
+    pub fn new(dx: isize, dy: isize) -> Slope {
+        Slope { dx, dy }
+    }
+}
+
+/// The greatest common divisor, or `None` when it is too large for an
+/// `isize`, as with `isize::MIN` and zero.
+fn gcd(a: isize, b: isize) -> Option<isize> {
+    if b == 0 {
+        a.checked_abs()
+    } else {
+        gcd(b, a.wrapping_rem(b))
+    }
+}
+
+/// Reads `n` or `n/d` as a fraction in lowest terms with a positive denominator.
+fn parse_fraction(str: &str) -> Result<(isize, isize), String> {
+    let mut parts = str.splitn(2, '/');
+    let numerator = parts.next().unwrap_or("").trim();
+    let denominator = parts.next().unwrap_or("1").trim();
+    let parse = |part: &str| {
+        part.parse::<isize>()
+            .map_err(|_| format!("expected a whole number or fraction, got {}", str))
+    };
+    let (numerator, denominator) = (parse(numerator)?, parse(denominator)?);
+    if denominator == 0 {
+        return Err(format!("{} divides by zero", str));
+    }
+    let too_large = || format!("{} is too large for a slope", str);
+    let divisor = gcd(numerator, denominator).ok_or_else(too_large)? * denominator.signum();
+    numerator
+        .checked_div(divisor)
+        .zip(denominator.checked_div(divisor))
+        .ok_or_else(too_large)
+}
+
+/// Reads `dx,dy`, where either may be a fraction such as `3/2`. A fractional
+/// slope only lands on whole cells every few rows, so `3/2,1` becomes the
+/// whole step `3,2`.
+impl FromStr for Slope {
+    type Err = String;
+
+    fn from_str(str: &str) -> Result<Slope, String> {
+        let mut parts = str.splitn(2, ',');
+        let (dx, dy) = match (parts.next(), parts.next()) {
+            (Some(dx), Some(dy)) => (parse_fraction(dx)?, parse_fraction(dy)?),
+            _ => return Err(format!("expected a slope as dx,dy, got {}", str)),
+        };
+        let too_large = || format!("slope {} is too large", str);
+        let scale = gcd(dx.1, dy.1)
+            .and_then(|divisor| (dx.1 / divisor).checked_mul(dy.1))
+            .ok_or_else(too_large)?;
+        let dx = dx.0.checked_mul(scale / dx.1).ok_or_else(too_large)?;
+        let dy = dy.0.checked_mul(scale / dy.1).ok_or_else(too_large)?;
+        Ok(Slope::new(dx, dy))
+    }
+}
+
+impl fmt::Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.dx, self.dy)
+    }
+}
+
+/// Where a toboggan went and how many trees it hit on the way.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Trajectory {
+    pub trees: usize,
+    /// Every cell the toboggan stopped on, in order, starting with where it began.
+    pub visited: Vec<(usize, usize)>,
+}
+
+/// The cells visited following `slope` from `start`, or from the top left
+/// when no start is given, until the toboggan leaves the map or, on a map
+/// that wraps, comes back around to where it started.
+///
+/// On a clamped map the toboggan ends up stuck against an edge, so the path
+/// also stops once a step leaves it where it was. No path is longer than the
+/// map has cells, since any longer one would have to be going round in circles.
+pub fn path<T>(
+    map: &Grid<T>,
+    slope: Slope,
+    start: Option<(isize, isize)>,
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut next = Some(start.unwrap_or((0, 0)));
+    let first = next.and_then(|(x, y)| map.locate(x, y));
+    let mut previous = None;
+    let mut remaining = map.width() * map.height();
+    std::iter::from_fn(move || {
+        let (x, y) = next?;
+        let position = map.locate(x, y)?;
+        if remaining == 0 || previous == Some(position) {
+            return None;
+        }
+        if previous.is_some() && Some(position) == first {
+            return None;
+        }
+        previous = Some(position);
+        remaining -= 1;
+        next = x.checked_add(slope.dx).zip(y.checked_add(slope.dy));
+        Some(position)
+    })
+}
+
+fn is_tree(map: &Grid<bool>, (x, y): (usize, usize)) -> bool {
+    map.get(x as isize, y as isize) == Some(&true)
+}
+
+/// Follows `slope` along its [`path`], keeping every cell visited.
+pub fn trajectory(map: &Grid<bool>, slope: Slope, start: Option<(isize, isize)>) -> Trajectory {
+    let visited = path(map, slope, start).collect::<Vec<(usize, usize)>>();
+    let trees = visited
+        .iter()
+        .filter(|&&position| is_tree(map, position))
+        .count();
+    Trajectory { trees, visited }
+}
+
+/// Counts the trees along a slope's [`path`] without keeping the cells.
+pub fn count_trees(map: &Grid<bool>, slope: Slope, start: Option<(isize, isize)>) -> usize {
+    path(map, slope, start)
+        .filter(|&position| is_tree(map, position))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::grid::Edges;
+
+    const MAP: &str = "..#.\n#...\n.#..\n..#.\n#..#";
+
+    fn map(edges: Edges) -> Grid<bool> {
+        MAP.parse::<Grid<bool>>().unwrap().with_edges(edges)
+    }
+
+    #[test]
+    fn can_parse_whole_and_fractional_slopes() {
+        assert_eq!(Ok(Slope::new(3, 1)), "3,1".parse());
+        assert_eq!(Ok(Slope::new(-2, -1)), "-2,-1".parse());
+        assert_eq!(Ok(Slope::new(3, 2)), "3/2,1".parse());
+        assert_eq!(Ok(Slope::new(3, 2)), "6/4,1".parse());
+        assert_eq!(Ok(Slope::new(-1, 6)), "1/-2,3".parse());
+        assert_eq!(Ok(Slope::new(6, 4)), "6,4".parse::<Slope>());
+        assert!("1/0,1".parse::<Slope>().is_err());
+        assert!("1".parse::<Slope>().is_err());
+    }
+
+    #[test]
+    fn reports_slopes_too_large_to_step_by() {
+        let reason = |str: &str| str.parse::<Slope>().unwrap_err();
+        assert_eq!(
+            "slope 1/9223372036854775807,1/9223372036854775806 is too large",
+            reason("1/9223372036854775807,1/9223372036854775806")
+        );
+        assert_eq!(
+            "slope 2/3,9223372036854775807 is too large",
+            reason("2/3,9223372036854775807")
+        );
+        assert_eq!(
+            "-9223372036854775808/-1 is too large for a slope",
+            reason("-9223372036854775808/-1,1")
+        );
+        assert_eq!(
+            "0/-9223372036854775808 is too large for a slope",
+            reason("0/-9223372036854775808,1")
+        );
+        assert_eq!(
+            Ok(Slope::new(isize::MIN, 1)),
+            "-9223372036854775808,1".parse::<Slope>()
+        );
+        assert_eq!(
+            Ok(Slope::new(-2, 1)),
+            "-9223372036854775808/4611686018427387904,1".parse::<Slope>()
+        );
+    }
+
+    #[test]
+    fn follows_a_slope_down_the_map() {
+        let result = trajectory(&map(Edges::WrapHorizontally), Slope::new(1, 1), None);
+        assert_eq!(vec![(0, 0), (1, 1), (2, 2), (3, 3), (0, 4)], result.visited);
+        assert_eq!(1, result.trees);
+    }
+
+    #[test]
+    fn can_travel_left_and_up_from_a_start() {
+        let result = trajectory(
+            &map(Edges::WrapHorizontally),
+            Slope::new(-1, -1),
+            Some((3, 4)),
+        );
+        assert_eq!(vec![(3, 4), (2, 3), (1, 2), (0, 1), (3, 0)], result.visited);
+        assert_eq!(4, result.trees);
+    }
+
+    #[test]
+    fn stops_where_a_wrapping_path_repeats() {
+        let result = trajectory(&map(Edges::Wrap), Slope::new(1, 0), Some((0, 1)));
+        assert_eq!(4, result.visited.len());
+        assert_eq!(1, result.trees);
+        let still = trajectory(&map(Edges::Wrap), Slope::new(0, 0), None);
+        assert_eq!(vec![(0, 0)], still.visited);
+    }
+
+    #[test]
+    fn stops_against_the_edge_of_a_clamped_map() {
+        let result = trajectory(&map(Edges::Clamp), Slope::new(1, 1), None);
+        assert_eq!(vec![(0, 0), (1, 1), (2, 2), (3, 3), (3, 4)], result.visited);
+        let sideways = trajectory(&map(Edges::Clamp), Slope::new(-1, 0), Some((2, 0)));
+        assert_eq!(vec![(2, 0), (1, 0), (0, 0)], sideways.visited);
+    }
+
+    #[test]
+    fn huge_steps_do_not_overflow() {
+        let huge = Slope::new(isize::MAX, isize::MAX);
+        let result = trajectory(&map(Edges::Wrap), huge, Some((1, 1)));
+        assert_eq!(Some(&(1, 1)), result.visited.first());
+        assert!(result.visited.len() <= 20);
+        let bounded = trajectory(&map(Edges::Bounded), huge, None);
+        assert_eq!(vec![(0, 0)], bounded.visited);
+    }
+
+    #[test]
+    fn starting_off_the_map_goes_nowhere() {
+        let result = trajectory(
+            &map(Edges::WrapHorizontally),
+            Slope::new(1, 1),
+            Some((0, 9)),
+        );
+        assert_eq!(0, result.visited.len());
+    }
+}
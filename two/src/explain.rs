@@ -0,0 +1,236 @@
+use crate::graphemes;
+use crate::rules::{Constraint, Entry};
+use serde::Serialize;
+
+/// Everything one constraint observed about a password, and whether the
+/// password passed it under each policy.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Diagnosis {
+    /// The constraint as it is written in the database.
+    pub rule: String,
+    pub character: String,
+    /// How many times the character appears in the password.
+    pub count: usize,
+    /// The allowed count, or `None` for a forbidden character.
+    pub range: Option<(usize, usize)>,
+    /// The characters at positions `min` and `max`, if the password is long enough.
+    pub at_min: Option<String>,
+    pub at_max: Option<String>,
+    pub count_passes: bool,
+    pub positions_passes: bool,
+}
+
+impl Constraint {
+    pub fn diagnose(&self, password: &str) -> Diagnosis {
+        let (rule, character, range, (at_min, at_max)) = match self {
+            Constraint::Rule(rule) => (
+                format!("{}-{} {}", rule.min, rule.max, rule.character),
+                &rule.character,
+                Some((*rule.min, *rule.max)),
+                rule.at_positions(password),
+            ),
+            Constraint::Without(character) => {
+                (format!("!{}", character), character, None, (None, None))
+            }
+        };
+        Diagnosis {
+            rule,
+            character: character.to_string(),
+            count: graphemes(password)
+                .into_iter()
+                .filter(|found| *found == &**character)
+                .count(),
+            range,
+            at_min: at_min.map(str::to_string),
+            at_max: at_max.map(str::to_string),
+            count_passes: self.allows_count(password),
+            positions_passes: self.allows_positions(password),
+        }
+    }
+}
+
+/// Why one line of the database passed or failed each policy.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Report {
+    /// The line of the database the entry is on.
+    pub line: usize,
+    pub password: String,
+    pub constraints: Vec<Diagnosis>,
+    pub count_passes: bool,
+    pub positions_passes: bool,
+}
+
+impl Report {
+    /// The constraints the password failed under the sled rental policy.
+    pub fn count_failures(&self) -> Vec<&str> {
+        self.constraints
+            .iter()
+            .filter(|diagnosis| !diagnosis.count_passes)
+            .map(|diagnosis| diagnosis.rule.as_str())
+            .collect()
+    }
+
+    /// The constraints the password failed under the corporate policy.
+    pub fn positions_failures(&self) -> Vec<&str> {
+        self.constraints
+            .iter()
+            .filter(|diagnosis| !diagnosis.positions_passes)
+            .map(|diagnosis| diagnosis.rule.as_str())
+            .collect()
+    }
+}
+
+pub fn explain(entries: &[Entry]) -> Vec<Report> {
+    entries
+        .iter()
+        .map(|entry| {
+            let constraints = entry
+                .constraints
+                .iter()
+                .map(|constraint| constraint.diagnose(&entry.password))
+                .collect::<Vec<Diagnosis>>();
+            Report {
+                line: entry.line,
+                password: entry.password.clone(),
+                count_passes: constraints.iter().all(|diagnosis| diagnosis.count_passes),
+                positions_passes: constraints
+                    .iter()
+                    .all(|diagnosis| diagnosis.positions_passes),
+                constraints,
+            }
+        })
+        .collect()
+}
+
+fn verdict(passes: bool) -> &'static str {
+    if passes {
+        "ok"
+    } else {
+        "FAIL"
+    }
+}
+
+fn found(character: &Option<String>) -> String {
+    character.clone().unwrap_or_else(|| "-".to_string())
+}
+
+/// Lays the reports out with one row per constraint.
+pub fn to_table(reports: &[Report]) -> String {
+    let mut rows = vec![[
+        "line",
+        "password",
+        "rule",
+        "count",
+        "range",
+        "at min",
+        "at max",
+        "count",
+        "positions",
+    ]
+    .iter()
+    .map(|heading| heading.to_string())
+    .collect::<Vec<String>>()];
+    for report in reports {
+        for diagnosis in &report.constraints {
+            rows.push(vec![
+                report.line.to_string(),
+                report.password.clone(),
+                diagnosis.rule.clone(),
+                diagnosis.count.to_string(),
+                diagnosis
+                    .range
+                    .map_or("none".to_string(), |(min, max)| format!("{}-{}", min, max)),
+                found(&diagnosis.at_min),
+                found(&diagnosis.at_max),
+                verdict(diagnosis.count_passes).to_string(),
+                verdict(diagnosis.positions_passes).to_string(),
+            ]);
+        }
+    }
+    let widths = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<usize>>();
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn to_json(reports: &[Report]) -> String {
+    serde_json::to_string_pretty(reports).expect("reports only hold plain data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::parse_entry;
+    use crate::PasswordPhilosophy;
+    use common::Solution;
+
+    #[test]
+    fn can_explain_a_failed_count() {
+        let entry = parse_entry("1-3 b: cdefg").unwrap();
+        let report = explain(&[entry]).remove(0);
+        let expected = Diagnosis {
+            rule: "1-3 b".to_string(),
+            character: "b".to_string(),
+            count: 0,
+            range: Some((1, 3)),
+            at_min: Some("c".to_string()),
+            at_max: Some("e".to_string()),
+            count_passes: false,
+            positions_passes: false,
+        };
+        assert_eq!(vec![expected], report.constraints);
+        assert_eq!(vec!["1-3 b"], report.count_failures());
+    }
+
+    #[test]
+    fn names_the_constraint_that_failed() {
+        let entry = parse_entry("1-3 a; !e: abcde").unwrap();
+        let report = explain(&[entry]).remove(0);
+        assert!(!report.count_passes);
+        assert_eq!(vec!["!e"], report.count_failures());
+        assert_eq!(vec!["!e"], report.positions_failures());
+        assert_eq!(None, report.constraints[1].at_min);
+    }
+
+    #[test]
+    fn can_render_table_and_json() {
+        let reports = explain(&[parse_entry("1-3 a: abcde").unwrap()]);
+        assert_eq!(
+            "line  password  rule   count  range  at min  at max  count  positions\n\
+             1     abcde     1-3 a  1      1-3    a       c       ok     ok",
+            to_table(&reports)
+        );
+        let json: serde_json::Value = serde_json::from_str(&to_json(&reports)).unwrap();
+        assert_eq!(
+            json[0]["constraints"][0]["range"],
+            serde_json::json!([1, 3])
+        );
+        assert_eq!(json[0]["positions_passes"], serde_json::json!(true));
+    }
+
+    #[test]
+    fn reports_the_line_each_entry_is_on() {
+        let parsed = PasswordPhilosophy::parse("1-3 a: abcde\n\nbad line\n1-3 b: cdefg\n");
+        let reports = explain(&parsed.value);
+        let lines = reports.iter().map(|report| report.line).collect::<Vec<_>>();
+        assert_eq!(vec![1, 4], lines);
+        let json: serde_json::Value = serde_json::from_str(&to_json(&reports)).unwrap();
+        assert_eq!(json[1]["line"], serde_json::json!(4));
+    }
+}
@@ -0,0 +1,321 @@
+use common::{input, Error, Parsed, Solution};
+use policy::{CountInRange, Policy, Positions};
+use rules::{Constraint, Entry};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub mod explain;
+pub mod policy;
+pub mod rules;
+pub mod stream;
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+/// A `min-max character` rule. The character is a single grapheme cluster,
+/// so a letter with a combining accent counts as one character, and the
+/// bounds are 1-based positions in graphemes when read as positions.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Verification {
+    pub max: Box<usize>,
+    pub min: Box<usize>,
+    pub character: Box<str>,
+}
+
+/// The user-perceived characters of `string`, in order.
+pub fn graphemes(string: &str) -> Vec<&str> {
+    string.graphemes(true).collect()
+}
+
+impl Verification {
+    pub fn is_valid(&self, string: &str) -> bool {
+        let amount = string
+            .graphemes(true)
+            .filter(|grapheme| *grapheme == &*self.character)
+            .count();
+        (*self.min..=*self.max).contains(&amount)
+    }
+
+    /// The grapheme at 1-based `position`, if the string is that long.
+    fn at<'a>(graphemes: &[&'a str], position: usize) -> Option<&'a str> {
+        graphemes.get(position.checked_sub(1)?).copied()
+    }
+
+    pub fn is_valid_updated(&self, string: &str) -> bool {
+        let characters = graphemes(string);
+        let pos_one_is_character =
+            Verification::at(&characters, *self.min) == Some(&*self.character);
+        let pos_two_is_character =
+            Verification::at(&characters, *self.max) == Some(&*self.character);
+
+        pos_one_is_character ^ pos_two_is_character
+    }
+
+    /// The graphemes at positions `min` and `max`.
+    pub fn at_positions<'a>(&self, string: &'a str) -> (Option<&'a str>, Option<&'a str>) {
+        let characters = graphemes(string);
+        (
+            Verification::at(&characters, *self.min),
+            Verification::at(&characters, *self.max),
+        )
+    }
+}
+
+/// Parses a `min-max character: password` line into its policy and password.
+pub fn parse(str: &str) -> Result<(Verification, String), Error> {
+    match rules::parse_entry(str)? {
+        Entry {
+            mut constraints,
+            password,
+            ..
+        } if constraints.len() == 1 => match constraints.pop() {
+            Some(Constraint::Rule(rule)) => Ok((rule, password)),
+            _ => Err(Error::parse(
+                str,
+                1,
+                "expected `min-max character: password`",
+            )),
+        },
+        _ => Err(Error::parse(
+            str,
+            1,
+            "expected `min-max character: password`",
+        )),
+    }
+}
+
+/// How many of the passwords `policy` accepts.
+pub fn count_allowed<P: Policy + ?Sized>(entries: &[Entry], policy: &P) -> usize {
+    entries
+        .iter()
+        .filter(|entry| policy.allows(&entry.constraints, &entry.password))
+        .count()
+}
+
+pub struct PasswordPhilosophy;
+
+impl Solution for PasswordPhilosophy {
+    const RECORDS: &'static str = "password lines";
+
+    type Input = Vec<Entry>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Parsed<Vec<Entry>> {
+        input::parse_numbered_lines(input, |number, line| {
+            rules::parse_entry(line).map(|entry| entry.on_line(number))
+        })
+    }
+
+    fn part_one(entries: &Vec<Entry>) -> Option<usize> {
+        Some(count_allowed(entries, &CountInRange))
+    }
+
+    fn part_two(entries: &Vec<Entry>) -> Option<usize> {
+        Some(count_allowed(entries, &Positions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_validate_updated_is_correct() {
+        let input = "aab";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid_updated(input);
+        assert!(result);
+    }
+
+    #[test]
+    fn can_validate_updated_is_incorrect_at_end() {
+        let input = "aaa";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid_updated(input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn when_validate_updated_has_neither_is_false() {
+        let input = "bab";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid_updated(input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn when_validate_updated_has_last_is_true() {
+        let input = "baa";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid_updated(input);
+        assert!(result);
+    }
+
+    #[test]
+    fn can_validate_updated_is_correct_when_no_char_exists_at_max() {
+        let input = "aa";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid_updated(input);
+        assert!(result);
+    }
+
+    #[test]
+    fn can_validate_when_at_upper_boundary() {
+        let input = "aaa";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid(input);
+        assert!(result);
+    }
+
+    #[test]
+    fn can_validate_when_at_lower_boundary() {
+        let input = "a";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid(input);
+        assert!(result);
+    }
+
+    #[test]
+    fn empty_input_always_fails() {
+        let input = "";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid(input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn can_validate_password_is_correct() {
+        let input = "abcde";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        }
+        .is_valid(input);
+        assert!(result);
+    }
+
+    #[test]
+    fn can_validate_password_is_incorrect() {
+        let input = "cdefg";
+        let result = Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "b".into(),
+        }
+        .is_valid(input);
+        assert!(!result);
+    }
+
+    #[test]
+    fn can_parse_input() {
+        let expected = (
+            Verification {
+                max: Box::new(3),
+                min: Box::new(1),
+                character: "a".into(),
+            },
+            "abcde".to_string(),
+        );
+        let input = "1-3 a: abcde";
+        let result = parse(input);
+        assert_eq!(Ok(expected), result);
+    }
+
+    #[test]
+    fn can_parse_input_with_large_numbers() {
+        let expected = (
+            Verification {
+                max: Box::new(200),
+                min: Box::new(30),
+                character: "c".into(),
+            },
+            "ccccccccc".to_string(),
+        );
+        let input = "30-200 c: ccccccccc";
+        let result = parse(input);
+        assert_eq!(Ok(expected), result);
+    }
+
+    #[test]
+    fn can_parse_input_with_bounds_past_a_byte() {
+        let expected = (
+            Verification {
+                max: Box::new(70000),
+                min: Box::new(300),
+                character: "c".into(),
+            },
+            "ccccccccc".to_string(),
+        );
+        let input = "300-70000 c: ccccccccc";
+        let result = parse(input);
+        assert_eq!(Ok(expected), result);
+    }
+
+    #[test]
+    fn reports_where_parsing_failed() {
+        let input = "1-0 a: abcde";
+        let expected = Error::Parse {
+            line: 1,
+            column: 3,
+            text: input.to_string(),
+            reason: "max must be at least 1, positions start at 1".to_string(),
+        };
+        assert_eq!(Err(expected), parse(input));
+    }
+
+    #[test]
+    fn validate_updated_does_not_panic_on_position_zero() {
+        let result = Verification {
+            max: Box::new(2),
+            min: Box::new(0),
+            character: "a".into(),
+        }
+        .is_valid_updated("ba");
+        assert!(result);
+    }
+
+    #[test]
+    fn positions_count_graphemes_not_chars() {
+        let rule = Verification {
+            max: Box::new(3),
+            min: Box::new(2),
+            character: "e\u{301}".into(),
+        };
+        assert!(rule.is_valid_updated("xe\u{301}yz"));
+        assert!(!rule.is_valid_updated("xe\u{301}e\u{301}"));
+        assert!(rule.is_valid("e\u{301}e\u{301}"));
+        assert!(!rule.is_valid("e"));
+    }
+}
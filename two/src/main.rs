@@ -1,243 +1,75 @@
-use regex::Regex;
-
-#[cfg(windows)]
-const LINE_ENDING: &'static str = "\r\n";
-#[cfg(not(windows))]
-const LINE_ENDING: &'static str = "\n";
-
-#[derive(Debug, PartialEq, Eq)]
-struct Verification {
-    pub max: Box<u8>,
-    pub min: Box<u8>,
-    pub character: Box<char>,
-}
-
-impl Verification {
-    pub fn is_valid<'a>(&self, string: &'a str) -> bool {
-        let amount = string
-            .chars()
-            .filter(|character| *self.character == *character)
-            .count();
-        (usize::from(*self.min)..=usize::from(*self.max)).contains(&amount)
-    }
-
-    pub fn is_valid_updated<'a>(&self, string: &'a str) -> bool {
-        let characters = string.chars().collect::<Vec<char>>();
-        let pos_one_is_character: Option<bool> = characters
-            .get(usize::from(*self.min - 1))
-            .map(|character| *self.character == *character);
-        let pos_two_is_character: Option<bool> = characters
-            .get(usize::from(*self.max - 1))
-            .map(|character| *self.character == *character);
-
-        pos_one_is_character.unwrap_or(false) ^ pos_two_is_character.unwrap_or(false)
-    }
-}
-
-fn parse<'a>(str: &'a str) -> Result<(Verification, String), &'a str> {
-    let re =
-        Regex::new(r"(?P<min>\d*)-(?P<max>\d*) (?P<character>\w{1}): (?P<password>\w*)").unwrap();
-    let caps = re.captures(str).ok_or("failed to get captures")?;
-    let max = caps["max"]
-        .parse::<u8>()
-        .map(|result| Box::new(result.clone()))
-        .map_err(|_| "no max!")?;
-
-    let min = caps["min"]
-        .parse::<u8>()
-        .map(|result| Box::new(result.clone()))
-        .map_err(|_| "no min!")?;
-
-    let character = caps["character"]
-        .chars()
-        .next()
-        .ok_or("no character")
-        .map(|result| Box::new(result.clone()))
-        .map_err(|_| "no min!")?;
-
-    let password = caps["password"].to_string();
-
-    let verification = Verification {
-        max,
-        min,
-        character,
-    };
-    Ok((verification, password))
-}
-
-fn main() {
-    let input = include_str!("input.txt");
-    let part_one_result = input
-        .split(LINE_ENDING)
-        .map(|string: &str| parse(string))
-        .filter_map(Result::ok)
-        .filter(|(validation, password)| validation.is_valid(password))
-        .count();
-
-    let part_two_result = input
-        .split(LINE_ENDING)
-        .map(|string: &str| parse(string))
-        .filter_map(Result::ok)
-        .filter(|(validation, password)| validation.is_valid_updated(password))
-        .count();
-
-    println!(
-        "part_one:={}, part_two:={}",
-        part_one_result, part_two_result
-    );
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn can_validate_updated_is_correct() {
-        let input = "aab";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
-        }
-        .is_valid_updated(input);
-        assert_eq!(true, result);
-    }
-
-    #[test]
-    fn can_validate_updated_is_incorrect_at_end() {
-        let input = "aaa";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
-        }
-        .is_valid_updated(input);
-        assert_eq!(false, result);
+use common::cli::{self, Args};
+use common::input;
+use common::Solution;
+use std::env;
+use std::process;
+use two::explain;
+use two::policy::{self, CountInRange, Policy, Positions};
+use two::stream;
+use two::{PasswordPhilosophy, DEFAULT_INPUT};
+
+const USAGE: &str =
+    "usage: two [--policy SPEC]... [--explain [--format table|json]] [--stream [--parallel]]";
+
+fn run(mut args: Vec<String>) -> Result<(), String> {
+    let mut policies: Vec<Box<dyn Policy>> = Vec::new();
+    while let Some(spec) = cli::take_option(&mut args, "--policy")? {
+        policies.push(policy::from_spec(&spec)?);
     }
-
-    #[test]
-    fn when_validate_updated_has_neither_is_false() {
-        let input = "bab";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
-        }
-        .is_valid_updated(input);
-        assert_eq!(false, result);
-    }
-
-    #[test]
-    fn when_validate_updated_has_last_is_true() {
-        let input = "baa";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
-        }
-        .is_valid_updated(input);
-        assert_eq!(true, result);
-    }
-
-    #[test]
-    fn can_validate_updated_is_correct_when_no_char_exists_at_max() {
-        let input = "aa";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
+    let explain = cli::take_flag(&mut args, "--explain");
+    let format = cli::take_option(&mut args, "--format")?;
+    let streaming = cli::take_flag(&mut args, "--stream");
+    let parallel = cli::take_flag(&mut args, "--parallel");
+    if streaming {
+        if explain {
+            return Err("--explain needs the whole database, so it cannot be streamed".to_string());
         }
-        .is_valid_updated(input);
-        assert_eq!(true, result);
-    }
-
-    #[test]
-    fn can_validate_when_at_upper_boundary() {
-        let input = "aaa";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
+        if policies.is_empty() {
+            policies = vec![Box::new(CountInRange), Box::new(Positions)];
         }
-        .is_valid(input);
-        assert_eq!(true, result);
+        return audit_stream(&Args::parse(&args)?, &policies, parallel);
     }
-
-    #[test]
-    fn can_validate_when_at_lower_boundary() {
-        let input = "a";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
-        }
-        .is_valid(input);
-        assert_eq!(true, result);
+    if policies.is_empty() && !explain {
+        cli::run::<PasswordPhilosophy>(&args, DEFAULT_INPUT);
+        return Ok(());
     }
 
-    #[test]
-    fn empty_input_always_fails() {
-        let input = "";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
+    let args = Args::parse(&args)?;
+    let entries = cli::load::<PasswordPhilosophy>(&args, DEFAULT_INPUT)?;
+    if explain {
+        let reports = explain::explain(&entries);
+        match format.as_deref() {
+            None | Some("table") => println!("{}", explain::to_table(&reports)),
+            Some("json") => println!("{}", explain::to_json(&reports)),
+            Some(other) => return Err(format!("--format must be table or json, got {}", other)),
         }
-        .is_valid(input);
-        assert_eq!(false, result);
     }
-
-    #[test]
-    fn can_validate_password_is_correct() {
-        let input = "abcde";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('a'),
-        }
-        .is_valid(input);
-        assert_eq!(true, result);
+    let accepted = policy::audit(&entries, &policies);
+    for (policy, count) in policies.iter().zip(accepted) {
+        println!("{}: {} of {} valid", policy.name(), count, entries.len());
     }
+    Ok(())
+}
 
-    #[test]
-    fn can_validate_password_is_incorrect() {
-        let input = "cdefg";
-        let result = Verification {
-            max: Box::new(3),
-            min: Box::new(1),
-            character: Box::new('b'),
-        }
-        .is_valid(input);
-        assert_eq!(false, result);
+/// Audits the database a line at a time, so it never has to fit in memory.
+fn audit_stream(args: &Args, policies: &[Box<dyn Policy>], parallel: bool) -> Result<(), String> {
+    let reader =
+        input::open(args.input.as_deref(), DEFAULT_INPUT).map_err(|err| err.to_string())?;
+    let audit =
+        stream::audit(reader, policies, args.mode, parallel).map_err(|err| err.to_string())?;
+    if let Some(summary) = cli::summary_of_counts(&audit.rejected, PasswordPhilosophy::RECORDS) {
+        eprintln!("{}", summary);
     }
-
-    #[test]
-    fn can_parse_input() {
-        let expected = (
-            Verification {
-                max: Box::new(3),
-                min: Box::new(1),
-                character: Box::new('a'),
-            },
-            "abcde".to_string(),
-        );
-        let input = "1-3 a: abcde";
-        let result = parse(input);
-        assert_eq!(Ok(expected), result);
+    for (policy, count) in policies.iter().zip(audit.accepted) {
+        println!("{}: {} of {} valid", policy.name(), count, audit.entries);
     }
+    Ok(())
+}
 
-    #[test]
-    fn can_parse_input_with_large_numbers() {
-        let expected = (
-            Verification {
-                max: Box::new(200),
-                min: Box::new(30),
-                character: Box::new('c'),
-            },
-            "ccccccccc".to_string(),
-        );
-        let input = "30-200 c: ccccccccc";
-        let result = parse(input);
-        assert_eq!(Ok(expected), result);
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(args) {
+        eprintln!("{}\n{} {}", err, USAGE, cli::USAGE);
+        process::exit(1);
     }
 }
@@ -0,0 +1,273 @@
+use crate::rules::{Constraint, Entry};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// A rule a password has to follow to be accepted.
+///
+/// Each password comes with the constraints written next to it in the
+/// database, so policies that interpret them get them alongside the password.
+/// Policies that apply to every password the same way are free to ignore them.
+/// Policies are shared between threads when a large database is audited in
+/// parallel.
+pub trait Policy: Send + Sync {
+    /// A short name for reporting how many passwords the policy accepted.
+    fn name(&self) -> String;
+
+    fn allows(&self, constraints: &[Constraint], password: &str) -> bool;
+}
+
+/// The sled rental policy: each character appears between `min` and `max`
+/// times, and forbidden characters not at all.
+pub struct CountInRange;
+
+impl Policy for CountInRange {
+    fn name(&self) -> String {
+        "count".to_string()
+    }
+
+    fn allows(&self, constraints: &[Constraint], password: &str) -> bool {
+        constraints
+            .iter()
+            .all(|constraint| constraint.allows_count(password))
+    }
+}
+
+/// The Official Toboggan Corporate Policy: each character is at exactly one
+/// of its two 1-based positions, and forbidden characters are nowhere.
+pub struct Positions;
+
+impl Policy for Positions {
+    fn name(&self) -> String {
+        "positions".to_string()
+    }
+
+    fn allows(&self, constraints: &[Constraint], password: &str) -> bool {
+        constraints
+            .iter()
+            .all(|constraint| constraint.allows_positions(password))
+    }
+}
+
+/// The password matches a regular expression somewhere.
+pub struct Matches(pub Regex);
+
+impl Policy for Matches {
+    fn name(&self) -> String {
+        format!("regex:{}", self.0)
+    }
+
+    fn allows(&self, _: &[Constraint], password: &str) -> bool {
+        self.0.is_match(password)
+    }
+}
+
+/// The password contains none of the given substrings.
+pub struct Forbids(pub Vec<String>);
+
+impl Policy for Forbids {
+    fn name(&self) -> String {
+        format!("forbid:{}", self.0.join(","))
+    }
+
+    fn allows(&self, _: &[Constraint], password: &str) -> bool {
+        !self
+            .0
+            .iter()
+            .any(|forbidden| password.contains(forbidden.as_str()))
+    }
+}
+
+/// The password has at least this many characters of each kind.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct CharacterClasses {
+    pub lower: usize,
+    pub upper: usize,
+    pub digit: usize,
+    pub symbol: usize,
+}
+
+impl Policy for CharacterClasses {
+    fn name(&self) -> String {
+        format!(
+            "classes:lower={},upper={},digit={},symbol={}",
+            self.lower, self.upper, self.digit, self.symbol
+        )
+    }
+
+    fn allows(&self, _: &[Constraint], password: &str) -> bool {
+        let mut found = CharacterClasses::default();
+        for character in password.chars() {
+            match character {
+                _ if character.is_lowercase() => found.lower += 1,
+                _ if character.is_uppercase() => found.upper += 1,
+                _ if character.is_numeric() => found.digit += 1,
+                _ if !character.is_whitespace() => found.symbol += 1,
+                _ => {}
+            }
+        }
+        found.lower >= self.lower
+            && found.upper >= self.upper
+            && found.digit >= self.digit
+            && found.symbol >= self.symbol
+    }
+}
+
+/// The password carries at least this many bits of Shannon entropy, judged
+/// by how often each of its own characters is repeated.
+pub struct Entropy(pub f64);
+
+/// The Shannon entropy of `password` in bits, over its whole length.
+pub fn entropy(password: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for character in password.chars() {
+        *counts.entry(character).or_insert(0) += 1;
+    }
+    let length = counts.values().sum::<usize>() as f64;
+    counts
+        .values()
+        .map(|&count| -(count as f64) * (count as f64 / length).log2())
+        .sum()
+}
+
+impl Policy for Entropy {
+    fn name(&self) -> String {
+        format!("entropy:{}", self.0)
+    }
+
+    fn allows(&self, _: &[Constraint], password: &str) -> bool {
+        entropy(password) >= self.0
+    }
+}
+
+fn parse_classes(spec: &str) -> Result<CharacterClasses, String> {
+    let mut classes = CharacterClasses::default();
+    for requirement in spec.split(',') {
+        let mut parts = requirement.splitn(2, '=');
+        let (class, count) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        let count = count
+            .parse::<usize>()
+            .map_err(|_| format!("expected `class=count` in classes, got {}", requirement))?;
+        match class {
+            "lower" => classes.lower = count,
+            "upper" => classes.upper = count,
+            "digit" => classes.digit = count,
+            "symbol" => classes.symbol = count,
+            _ => {
+                return Err(format!(
+                    "unknown class {}, expected lower, upper, digit or symbol",
+                    class
+                ))
+            }
+        }
+    }
+    Ok(classes)
+}
+
+/// Builds a policy from how it is named on the command line: `count`,
+/// `positions`, `regex:PATTERN`, `forbid:A,B`,
+/// `classes:lower=N,upper=N,digit=N,symbol=N` or `entropy:BITS`.
+pub fn from_spec(spec: &str) -> Result<Box<dyn Policy>, String> {
+    let mut parts = spec.splitn(2, ':');
+    let (kind, argument) = (parts.next().unwrap_or(""), parts.next());
+    match (kind, argument) {
+        ("count", None) => Ok(Box::new(CountInRange)),
+        ("positions", None) => Ok(Box::new(Positions)),
+        ("regex", Some(pattern)) => Regex::new(pattern)
+            .map(|regex| Box::new(Matches(regex)) as Box<dyn Policy>)
+            .map_err(|err| format!("invalid regex policy: {}", err)),
+        ("forbid", Some(substrings)) => Ok(Box::new(Forbids(
+            substrings
+                .split(',')
+                .filter(|substring| !substring.is_empty())
+                .map(|substring| substring.to_string())
+                .collect(),
+        ))),
+        ("classes", Some(classes)) => Ok(Box::new(parse_classes(classes)?)),
+        ("entropy", Some(bits)) => bits
+            .parse::<f64>()
+            .map(|bits| Box::new(Entropy(bits)) as Box<dyn Policy>)
+            .map_err(|_| format!("entropy needs a number of bits, got {}", bits)),
+        _ => Err(format!("unknown policy {}", spec)),
+    }
+}
+
+/// Counts how many passwords each policy accepts, going through them once.
+pub fn audit(entries: &[Entry], policies: &[Box<dyn Policy>]) -> Vec<usize> {
+    let mut accepted = vec![0; policies.len()];
+    for entry in entries {
+        for (count, policy) in accepted.iter_mut().zip(policies) {
+            if policy.allows(&entry.constraints, &entry.password) {
+                *count += 1;
+            }
+        }
+    }
+    accepted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Verification;
+
+    fn rule() -> Vec<Constraint> {
+        vec![Constraint::Rule(Verification {
+            max: Box::new(3),
+            min: Box::new(1),
+            character: "a".into(),
+        })]
+    }
+
+    #[test]
+    fn can_parse_policies() {
+        for spec in &[
+            "count",
+            "positions",
+            "regex:^[a-z]+$",
+            "forbid:password,1234",
+            "classes:lower=1,digit=2",
+            "entropy:2.5",
+        ] {
+            assert!(from_spec(spec).is_ok(), "{}", spec);
+        }
+        assert!(from_spec("count:1").is_err());
+        assert!(from_spec("regex:(").is_err());
+        assert!(from_spec("classes:emoji=1").is_err());
+        assert!(from_spec("entropy:lots").is_err());
+    }
+
+    #[test]
+    fn character_classes_need_enough_of_each() {
+        let policy = parse_classes("lower=2,upper=1,digit=1,symbol=1").unwrap();
+        assert!(policy.allows(&rule(), "abC1!"));
+        assert!(!policy.allows(&rule(), "aC1!"));
+        assert!(!policy.allows(&rule(), "abC1"));
+    }
+
+    #[test]
+    fn can_measure_entropy() {
+        assert_eq!(0.0, entropy("aaaa"));
+        assert_eq!(4.0, entropy("abab"));
+        assert_eq!(8.0, entropy("abcdefgh") / 3.0);
+        assert!(!Entropy(4.5).allows(&rule(), "abab"));
+    }
+
+    #[test]
+    fn audits_every_policy_in_one_pass() {
+        let entries = ["abcde", "password", "bbb"]
+            .iter()
+            .enumerate()
+            .map(|(index, password)| Entry {
+                line: index + 1,
+                constraints: rule(),
+                password: password.to_string(),
+            })
+            .collect::<Vec<Entry>>();
+        let policies = vec![
+            from_spec("count").unwrap(),
+            from_spec("positions").unwrap(),
+            from_spec("forbid:pass").unwrap(),
+            from_spec("regex:^b+$").unwrap(),
+        ];
+        assert_eq!(vec![2, 1, 2, 1], audit(&entries, &policies));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::Verification;
+use common::Error;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// One constraint a line of the database puts on its password.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// `min-max c`, read as a count range or as two positions depending on
+    /// the policy.
+    Rule(Verification),
+    /// `!c`: the character must not appear at all.
+    Without(Box<str>),
+}
+
+impl Constraint {
+    /// Whether the password passes this constraint under the sled rental policy.
+    pub fn allows_count(&self, password: &str) -> bool {
+        match self {
+            Constraint::Rule(rule) => rule.is_valid(password),
+            Constraint::Without(character) => !contains(password, character),
+        }
+    }
+
+    /// Whether the password passes this constraint under the corporate policy.
+    pub fn allows_positions(&self, password: &str) -> bool {
+        match self {
+            Constraint::Rule(rule) => rule.is_valid_updated(password),
+            Constraint::Without(character) => !contains(password, character),
+        }
+    }
+}
+
+/// Whether `grapheme` is one of the graphemes of `password`, so a forbidden
+/// `e` is not found inside an `é` written with a combining accent.
+fn contains(password: &str, grapheme: &str) -> bool {
+    password.graphemes(true).any(|found| found == grapheme)
+}
+
+/// A line of the database: every constraint before the `:` and the password after it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The line of the database the entry is on.
+    pub line: usize,
+    pub constraints: Vec<Constraint>,
+    pub password: String,
+}
+
+impl Entry {
+    /// The same entry, found on `line` of the database.
+    pub fn on_line(mut self, line: usize) -> Entry {
+        self.line = line;
+        self
+    }
+}
+
+/// Walks a line one character at a time, remembering the byte offset so
+/// errors can point at the exact column.
+struct Cursor<'a> {
+    line: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn peek(&self) -> Option<char> {
+        self.line[self.offset..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let next = self.peek()?;
+        self.offset += next.len_utf8();
+        Some(next)
+    }
+
+    fn error<S: Into<String>>(&self, reason: S) -> Error {
+        Error::parse_at(self.line, self.offset, reason)
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek() == Some(' ') {
+            self.bump();
+        }
+    }
+
+    fn expect(&mut self, expected: char, reason: &str) -> Result<(), Error> {
+        match self.peek() {
+            Some(next) if next == expected => {
+                self.bump();
+                Ok(())
+            }
+            _ => Err(self.error(reason)),
+        }
+    }
+
+    fn number(&mut self, name: &str) -> Result<usize, Error> {
+        let start = self.offset;
+        while self.peek().is_some_and(|next| next.is_ascii_digit()) {
+            self.bump();
+        }
+        if start == self.offset {
+            return Err(self.error(format!("expected {}", name)));
+        }
+        match self.line[start..self.offset].parse::<usize>() {
+            Ok(0) => Err(Error::parse_at(
+                self.line,
+                start,
+                format!("{} must be at least 1, positions start at 1", name),
+            )),
+            Ok(number) => Ok(number),
+            Err(_) => Err(Error::parse_at(
+                self.line,
+                start,
+                format!("{} is too large", name),
+            )),
+        }
+    }
+
+    /// A single grapheme cluster, which may be any character other than
+    /// whitespace or the `;` and `:` separators.
+    fn character(&mut self) -> Result<Box<str>, Error> {
+        match self.line[self.offset..].graphemes(true).next() {
+            Some(next) if !next.trim().is_empty() && next != ":" && next != ";" => {
+                self.offset += next.len();
+                Ok(next.into())
+            }
+            _ => Err(self.error("expected a character")),
+        }
+    }
+
+    fn constraint(&mut self) -> Result<Constraint, Error> {
+        match self.peek() {
+            Some('!') => {
+                self.bump();
+                Ok(Constraint::Without(self.character()?))
+            }
+            Some(next) if next.is_ascii_digit() => {
+                let min = self.number("min")?;
+                self.expect('-', "expected `-` between min and max")?;
+                let max = self.number("max")?;
+                self.expect(' ', "expected a space before the character")?;
+                let character = self.character()?;
+                Ok(Constraint::Rule(Verification {
+                    max: Box::new(max),
+                    min: Box::new(min),
+                    character,
+                }))
+            }
+            _ => Err(self.error("expected `min-max character` or `!character`")),
+        }
+    }
+}
+
+/// Parses a line of constraints separated by `;`, then `:` and the password,
+/// such as `1-3 a; 2-4 b; !x: password`. A line with a single
+/// `min-max character` rule is the original database format. The entry is
+/// taken to be on line 1 until [`Entry::on_line`] says otherwise.
+pub fn parse_entry(line: &str) -> Result<Entry, Error> {
+    let mut cursor = Cursor { line, offset: 0 };
+    let mut constraints = vec![cursor.constraint()?];
+    loop {
+        cursor.skip_spaces();
+        match cursor.peek() {
+            Some(';') => {
+                cursor.bump();
+                cursor.skip_spaces();
+                constraints.push(cursor.constraint()?);
+            }
+            Some(':') => {
+                cursor.bump();
+                break;
+            }
+            _ => return Err(cursor.error("expected `;` or `:` after a constraint")),
+        }
+    }
+    cursor.skip_spaces();
+    let password = line[cursor.offset..].trim_end().to_string();
+    Ok(Entry {
+        line: 1,
+        constraints,
+        password,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(min: usize, max: usize, character: &str) -> Constraint {
+        Constraint::Rule(Verification {
+            max: Box::new(max),
+            min: Box::new(min),
+            character: character.into(),
+        })
+    }
+
+    fn entry(constraints: Vec<Constraint>, password: String) -> Entry {
+        Entry {
+            line: 1,
+            constraints,
+            password,
+        }
+    }
+
+    #[test]
+    fn can_parse_original_format() {
+        let expected = entry(vec![rule(1, 3, "a")], "abcde".to_string());
+        assert_eq!(Ok(expected), parse_entry("1-3 a: abcde"));
+    }
+
+    #[test]
+    fn can_parse_several_constraints() {
+        let expected = entry(
+            vec![
+                rule(1, 3, "a"),
+                rule(2, 4, "b"),
+                Constraint::Without("x".into()),
+            ],
+            "password".to_string(),
+        );
+        assert_eq!(Ok(expected), parse_entry("1-3 a; 2-4 b; !x: password"));
+        let expected = entry(
+            vec![rule(1, 3, "a"), Constraint::Without("x".into())],
+            "abc".to_string(),
+        );
+        assert_eq!(Ok(expected), parse_entry("1-3 a;!x:abc"));
+    }
+
+    #[test]
+    fn reports_the_column_of_a_bad_constraint() {
+        let error = |line: &str| parse_entry(line).unwrap_err().to_string();
+        assert_eq!(
+            "line 1, column 8: expected `min-max character` or `!character` in \"1-3 a; x: abc\"",
+            error("1-3 a; x: abc")
+        );
+        assert_eq!(
+            "line 1, column 8: min must be at least 1, positions start at 1 in \"1-3 a; 0-4 b: abc\"",
+            error("1-3 a; 0-4 b: abc")
+        );
+        assert_eq!(
+            "line 1, column 6: expected `;` or `:` after a constraint in \"1-3 ab: abc\"",
+            error("1-3 ab: abc")
+        );
+        assert_eq!(
+            "line 1, column 2: expected a character in \"!: abc\"",
+            error("!: abc")
+        );
+    }
+
+    #[test]
+    fn can_parse_unicode_characters_and_punctuation() {
+        let expected = entry(
+            vec![rule(1, 300, "é"), Constraint::Without("🦀".into())],
+            "p@ss: wörd!".to_string(),
+        );
+        assert_eq!(Ok(expected), parse_entry("1-300 é; !🦀: p@ss: wörd!"));
+        let combining = "e\u{301}";
+        let line = format!("2-4 {}: x{}", combining, combining);
+        assert_eq!(
+            Ok(entry(
+                vec![rule(2, 4, combining)],
+                format!("x{}", combining)
+            )),
+            parse_entry(&line)
+        );
+        assert_eq!(
+            "line 1, column 3: max is too large in \"1-99999999999999999999999 a: abc\"",
+            parse_entry("1-99999999999999999999999 a: abc")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn forbidden_characters_fail_both_policies() {
+        let constraint = Constraint::Without("x".into());
+        assert!(constraint.allows_count("abc"));
+        assert!(!constraint.allows_count("axc"));
+        assert!(!constraint.allows_positions("axc"));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::policy::Policy;
+use crate::rules;
+use common::cli::Mode;
+use common::Error;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+/// How many lines are read before they are checked, which bounds how much of
+/// the database is held in memory at once.
+pub const CHUNK: usize = 16 * 1024;
+
+/// What auditing a stream of password lines found. Only counts are kept, so
+/// the size of the audit does not grow with the database.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct Audit {
+    /// How many lines parsed.
+    pub entries: usize,
+    /// How many passwords each policy accepted, in the order the policies were given.
+    pub accepted: Vec<usize>,
+    /// How many lines were rejected for each reason.
+    pub rejected: BTreeMap<String, usize>,
+}
+
+/// Parses one line and checks it against every policy.
+fn check(line: &str, number: usize, policies: &[Box<dyn Policy>]) -> Result<Vec<bool>, Error> {
+    let entry = rules::parse_entry(line).map_err(|error| error.starting_at(number))?;
+    Ok(policies
+        .iter()
+        .map(|policy| policy.allows(&entry.constraints, &entry.password))
+        .collect())
+}
+
+impl Audit {
+    fn record(&mut self, result: Result<Vec<bool>, Error>, mode: Mode) -> Result<(), Error> {
+        match result {
+            Ok(passes) => {
+                self.entries += 1;
+                for (accepted, passed) in self.accepted.iter_mut().zip(passes) {
+                    if passed {
+                        *accepted += 1;
+                    }
+                }
+            }
+            Err(error) if mode == Mode::Strict => return Err(error),
+            Err(error) => *self.rejected.entry(error.reason().to_string()).or_insert(0) += 1,
+        }
+        Ok(())
+    }
+}
+
+/// Audits the database in `reader` against every policy in a single pass,
+/// reading it [`CHUNK`] lines at a time and checking each chunk across
+/// threads when `parallel` is set.
+pub fn audit<R: BufRead>(
+    reader: R,
+    policies: &[Box<dyn Policy>],
+    mode: Mode,
+    parallel: bool,
+) -> Result<Audit, Error> {
+    let mut audit = Audit {
+        accepted: vec![0; policies.len()],
+        ..Audit::default()
+    };
+    let mut lines = reader.lines().enumerate();
+    let mut chunk: Vec<(usize, String)> = Vec::with_capacity(CHUNK);
+    loop {
+        chunk.clear();
+        let mut read = 0;
+        for (index, line) in lines.by_ref().take(CHUNK) {
+            read += 1;
+            let line = line.map_err(|err| {
+                Error::Input(format!("could not read line {}: {}", index + 1, err))
+            })?;
+            if !line.trim().is_empty() {
+                chunk.push((index + 1, line));
+            }
+        }
+        let results: Vec<Result<Vec<bool>, Error>> = if parallel {
+            chunk
+                .par_iter()
+                .map(|(number, line)| check(line.trim_end_matches('\r'), *number, policies))
+                .collect()
+        } else {
+            chunk
+                .iter()
+                .map(|(number, line)| check(line.trim_end_matches('\r'), *number, policies))
+                .collect()
+        };
+        for result in results {
+            audit.record(result, mode)?;
+        }
+        if read < CHUNK {
+            return Ok(audit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{CountInRange, Positions};
+
+    fn policies() -> Vec<Box<dyn Policy>> {
+        vec![Box::new(CountInRange), Box::new(Positions)]
+    }
+
+    #[test]
+    fn audits_both_policies_in_one_pass() {
+        let input = "1-3 a: abcde\r\n1-3 b: cdefg\r\n\r\n2-9 c: ccccccccc\r\n";
+        let expected = Audit {
+            entries: 3,
+            accepted: vec![2, 1],
+            rejected: BTreeMap::new(),
+        };
+        for &parallel in &[false, true] {
+            let result = audit(input.as_bytes(), &policies(), Mode::Strict, parallel);
+            assert_eq!(Ok(&expected), result.as_ref());
+        }
+    }
+
+    #[test]
+    fn counts_rejected_lines_when_lenient() {
+        let input = "1-3 a: abcde\n0-3 a: abcde\nnonsense\n1-3 b b: cdefg";
+        let lenient = audit(input.as_bytes(), &policies(), Mode::Lenient, false).unwrap();
+        assert_eq!(1, lenient.entries);
+        assert_eq!(3, lenient.rejected.values().sum::<usize>());
+        let error = audit(input.as_bytes(), &policies(), Mode::Strict, true);
+        assert_eq!(
+            Err("line 2, column 1: min must be at least 1, positions start at 1 in \"0-3 a: abcde\""
+                .to_string()),
+            error.map_err(|error| error.to_string())
+        );
+    }
+
+    #[test]
+    fn handles_more_lines_than_fit_in_a_chunk() {
+        let lines = CHUNK * 2 + 17;
+        let input = "1-3 a: abcde\n".repeat(lines);
+        let result = audit(input.as_bytes(), &policies(), Mode::Strict, true).unwrap();
+        assert_eq!(lines, result.entries);
+        assert_eq!(vec![lines, lines], result.accepted);
+    }
+}